@@ -1,28 +1,12 @@
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use regex::{Captures, Regex};
-use syn::{parse_macro_input, DeriveInput};
-
-fn to_snake_case(value: String) -> String {
-    let re1 = Regex::new(r"([A-Z]+)([A-Z][a-z])").unwrap();
-    let re2 = Regex::new(r"([a-z]|\d)([A-Z])").unwrap();
-
-    let replaced1 = re1.replace_all(&value, |caps: &Captures| {
-        format!("{}_{}", &caps[1], &caps[2])
-    });
-
-    let replaced2 = re2.replace_all(&replaced1, |caps: &Captures| {
-        format!("{}_{}", &caps[1], &caps[2])
-    });
-
-    replaced2.to_lowercase()
-}
+use syn::{parse_macro_input, Data, DeriveInput};
 
 #[proc_macro_derive(Language)]
 pub fn derive_language(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let language = input.ident;
-    let language_name = format_ident!("{}", to_snake_case(language.to_string()));
+    let language_name = format_ident!("{}", language.to_string().to_lowercase());
 
     let expanded = quote! {
         impl Language for #language {
@@ -35,11 +19,34 @@ pub fn derive_language(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
-#[proc_macro_derive(TestFrameworkMeta)]
+#[proc_macro_derive(TestFrameworkMeta, attributes(enabled))]
 pub fn derive_test_framework_meta(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let test_framework = input.ident;
-    let test_framework_name = format_ident!("{}", to_snake_case(test_framework.to_string()));
+    let test_framework_name = format_ident!("{}", test_framework.to_string().to_lowercase());
+    // The field marked with `#[enabled]` opts in to a test framework that isn't detected
+    // automatically, the rest of them ignore `enabled = true`
+    let enabled_field = match &input.data {
+        Data::Struct(data) => data
+            .fields
+            .iter()
+            .find(|field| {
+                field
+                    .attrs
+                    .iter()
+                    .any(|attr| attr.path().is_ident("enabled"))
+            })
+            .and_then(|field| field.ident.clone()),
+        _ => None,
+    };
+    let configure_enabled = match enabled_field {
+        Some(field) => quote! {
+            if let Some(enabled) = config.enabled {
+                self.#field = enabled;
+            }
+        },
+        None => quote! {},
+    };
 
     let expanded = quote! {
         impl TestFrameworkMeta for #test_framework {
@@ -59,22 +66,48 @@ pub fn derive_test_framework_meta(input: TokenStream) -> TokenStream {
                 if self.executable.is_empty() {
                     None
                 } else {
-                    Some(self.executable.clone().into_iter().map(|s| s.to_string()).collect())
+                    Some(self.executable.clone())
                 }
             }
 
             fn args(&self) -> crate::ArgsList {
-                self.args.clone().into_iter().map(|s| s.to_string()).collect()
+                self.args.clone()
             }
 
-
             fn test_pattern(&self) -> &str {
                 &self.test_pattern
             }
 
+            fn projections(&self) -> &[crate::alternate::Projection] {
+                &self.projections
+            }
+
             fn namespace_pattern(&self) -> &str {
                 &self.namespace_pattern
             }
+
+            fn configure(&mut self, config: &crate::config::FrameworkConfig) {
+                if let Some(pattern) = &config.pattern {
+                    self.pattern = pattern.clone();
+                }
+                if let Some(executable) = &config.executable {
+                    self.executable = executable.clone();
+                }
+                if let Some(args) = &config.args {
+                    self.args = args.clone();
+                }
+                if let Some(test_pattern) = &config.test_pattern {
+                    self.test_pattern = test_pattern.clone();
+                }
+                if let Some(namespace_pattern) = &config.namespace_pattern {
+                    self.namespace_pattern = namespace_pattern.clone();
+                }
+                if let Some(projections) = &config.projections {
+                    // The configured projections take precedence over the conventions
+                    self.projections = [projections.clone(), self.projections.clone()].concat();
+                }
+                #configure_enabled
+            }
         }
     };
 
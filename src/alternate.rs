@@ -0,0 +1,200 @@
+use crate::{registry::Registry, test_framework::TestFramework, Context};
+use regex::Regex;
+use serde::Deserialize;
+use std::path::Path;
+
+const DIRS: &str = "**/";
+const NAME: &str = "*";
+
+/// Maps the source files to their test files and back, e.g. `app/**/*.rb` to
+/// `spec/**/*_spec.rb`.
+///
+/// `**/` matches any number of directories and `*` a part of a file name. The placeholders of
+/// one side are filled in order with the values matched by the other side, so `lib/**/*.ex`
+/// maps `lib/foo/bar.ex` to `test/foo/bar_test.exs` with `test/**/*_test.exs`.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Projection {
+    pub source: String,
+    pub test: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Placeholder {
+    Dirs,
+    Name,
+}
+
+impl Projection {
+    pub fn new(source: &str, test: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            test: test.to_string(),
+        }
+    }
+
+    /// The test file of the source file, if the source side matches it.
+    pub fn test_for(&self, source: &str) -> Option<String> {
+        project(&self.source, &self.test, source)
+    }
+
+    /// The source file of the test file, if the test side matches it.
+    pub fn source_for(&self, test: &str) -> Option<String> {
+        project(&self.test, &self.source, test)
+    }
+}
+
+/// Splits the pattern into the literal parts and the placeholders.
+fn tokenize(pattern: &str) -> Vec<Result<&str, Placeholder>> {
+    let mut tokens = vec![];
+    let mut rest = pattern;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix(DIRS) {
+            tokens.push(Err(Placeholder::Dirs));
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix(NAME) {
+            tokens.push(Err(Placeholder::Name));
+            rest = after;
+        } else {
+            let end = rest.find(NAME).unwrap_or(rest.len());
+            tokens.push(Ok(&rest[..end]));
+            rest = &rest[end..];
+        }
+    }
+
+    tokens
+}
+
+fn project(from: &str, to: &str, path: &str) -> Option<String> {
+    let from_tokens = tokenize(from);
+    let pattern = from_tokens
+        .iter()
+        .map(|token| match token {
+            Ok(literal) => regex::escape(literal),
+            Err(Placeholder::Dirs) => "((?:[^/]+/)*)".into(),
+            Err(Placeholder::Name) => "([^/]+)".into(),
+        })
+        .collect::<String>();
+    let captures = Regex::new(&format!("^{}$", pattern)).ok()?.captures(path)?;
+    let mut values = from_tokens
+        .iter()
+        .filter_map(|token| token.err())
+        .zip(captures.iter().skip(1))
+        .map(|(placeholder, value)| (placeholder, value.map_or("", |m| m.as_str())))
+        .collect::<Vec<_>>();
+    let mut result = String::new();
+
+    for token in tokenize(to) {
+        match token {
+            Ok(literal) => result.push_str(literal),
+            Err(placeholder) => {
+                // Every placeholder must have a value, the other side may drop some of them
+                let index = values.iter().position(|(other, _)| *other == placeholder)?;
+                result.push_str(values.remove(index).1);
+            }
+        }
+    }
+
+    Some(result)
+}
+
+/// Finds the existing test files of a source file, following the projections of all the test
+/// frameworks that are suitable for the test files.
+pub(crate) fn find_tests(registry: &Registry, context: &Context) -> Vec<Context> {
+    let mut tests: Vec<Context> = vec![];
+
+    for test_framework in registry.frameworks() {
+        // The projections are relative to the project of the test framework
+        let context = context.within_project(test_framework.root_markers());
+
+        for projection in test_framework.projections() {
+            let Some(path) = projection.test_for(&rel_str(&context)) else {
+                continue;
+            };
+
+            if let Some(test) = existing(&context, &path) {
+                let is_found = tests.iter().any(|other| other.path() == test.path());
+
+                if !is_found && test_framework.is_suitable_for(&test) {
+                    tests.push(test);
+                }
+            }
+        }
+    }
+
+    tests
+}
+
+/// Finds the existing source file of a test file, following the projections of its test
+/// framework.
+pub(crate) fn find_source(
+    test_framework: &dyn TestFramework,
+    context: &Context,
+) -> Option<Context> {
+    test_framework
+        .projections()
+        .iter()
+        .filter_map(|projection| projection.source_for(&rel_str(context)))
+        .find_map(|path| existing(context, &path))
+}
+
+/// The relative path with forward slashes, the projections use them on every platform.
+fn rel_str(context: &Context) -> String {
+    context.rel_str().replace('\\', "/")
+}
+
+fn existing(context: &Context, path: &str) -> Option<Context> {
+    if !context.root().join(Path::new(path)).is_file() {
+        return None;
+    }
+
+    context.for_file(path).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_projection() {
+        let projection = Projection::new("app/**/*.rb", "spec/**/*_spec.rb");
+
+        assert_eq!(
+            projection.test_for("app/models/user.rb").as_deref(),
+            Some("spec/models/user_spec.rb")
+        );
+        assert_eq!(
+            projection.test_for("app/user.rb").as_deref(),
+            Some("spec/user_spec.rb")
+        );
+        assert_eq!(
+            projection.source_for("spec/models/user_spec.rb").as_deref(),
+            Some("app/models/user.rb")
+        );
+        assert_eq!(projection.test_for("lib/user.rb"), None);
+        assert_eq!(projection.source_for("spec/models/user.rb"), None);
+    }
+
+    #[test]
+    fn test_projection_dropped_placeholder() {
+        let projection = Projection::new("**/*.py", "tests/test_*.py");
+
+        assert_eq!(
+            projection.test_for("pkg/billing.py").as_deref(),
+            Some("tests/test_billing.py")
+        );
+        assert_eq!(projection.source_for("tests/test_billing.py"), None);
+
+        let projection = Projection::new("**/*.ts", "**/__tests__/*.ts");
+
+        assert_eq!(
+            projection.test_for("src/a.ts").as_deref(),
+            Some("src/__tests__/a.ts")
+        );
+        assert_eq!(
+            projection.source_for("src/__tests__/a.ts").as_deref(),
+            Some("src/a.ts")
+        );
+    }
+}
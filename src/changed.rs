@@ -0,0 +1,99 @@
+use crate::{alternate, config::Config, registry::Registry, Context, Error, RelPath, Scope};
+use std::{path::Path, process::Command};
+
+/// Finds the test files affected by the changes relative to the git ref (`HEAD` by default),
+/// including the uncommitted and the untracked files.
+///
+/// The changed test files are run as they are, and the rest of the files are replaced with
+/// their tests following the projections (e.g. `lib/foo.rb` with `spec/foo_spec.rb`).
+pub fn changed_contexts(root: Option<&str>, base: Option<&str>) -> Result<Vec<Context>, Error> {
+    let root_path = RelPath::new(root, ".")?.root().clone();
+    let root = root_path.to_str();
+    let config = Config::discover(&root_path)?;
+    let registry = Registry::new(&config);
+    let mut contexts: Vec<Context> = vec![];
+
+    for file in changed_files(&root_path, base)? {
+        // Deleted files have nothing to run
+        if !root_path.join(&file).is_file() {
+            continue;
+        }
+
+        let context = Context::new(root, &file, None, Some(Scope::File), None)?;
+        let tests = if registry.find(&context).is_ok() {
+            vec![context]
+        } else {
+            alternate::find_tests(&registry, &context)
+        };
+
+        if tests.is_empty() {
+            log::info!("No tests found for {}", file);
+        }
+
+        for test in tests {
+            if !contexts.iter().any(|context| context.path() == test.path()) {
+                contexts.push(test);
+            }
+        }
+    }
+
+    Ok(contexts)
+}
+
+/// The files changed relative to the merge base of the ref and `HEAD`, relative to the root.
+fn changed_files(root: &Path, base: Option<&str>) -> Result<Vec<String>, Error> {
+    let rev = match base {
+        Some(base) => {
+            // Without a common ancestor git exits with an error but prints nothing
+            let rev = git(root, &["merge-base", base, "HEAD"]).map_err(|error| match error {
+                Error::Git { stderr, .. } if stderr.is_empty() => {
+                    Error::NoMergeBase(base.to_string())
+                }
+                error => error,
+            })?;
+
+            if rev.trim().is_empty() {
+                return Err(Error::NoMergeBase(base.to_string()));
+            }
+            rev.trim().to_string()
+        }
+        None => "HEAD".into(),
+    };
+    // Without `-z` git quotes the unusual paths (e.g. non-ASCII ones)
+    let mut files = paths(&git(
+        root,
+        &["diff", "--name-only", "--relative", "-z", &rev],
+    )?);
+
+    for file in paths(&git(
+        root,
+        &["ls-files", "--others", "--exclude-standard", "-z"],
+    )?) {
+        if !files.contains(&file) {
+            files.push(file);
+        }
+    }
+
+    Ok(files)
+}
+
+fn paths(output: &str) -> Vec<String> {
+    output
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(|path| path.to_string())
+        .collect()
+}
+
+fn git(root: &Path, args: &[&str]) -> Result<String, Error> {
+    let output = Command::new("git").args(args).current_dir(root).output()?;
+
+    if !output.status.success() {
+        return Err(Error::Git {
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
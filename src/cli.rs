@@ -1,48 +1,273 @@
-use anytest::{Context, LineNr, Scope};
-use clap::Parser;
+use crate::state;
+use anytest::{Context, LineNr, Scope, Shell};
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand, ValueEnum};
 use regex::Regex;
-use std::error::Error;
+use std::{env, error::Error, fmt};
 
 const PATH_REGEX: &str = r"^(.*?)(?::(\d*))?$";
 
 /// Run any test from your terminal.
 #[derive(Parser, Debug)]
-#[command(version, about, long_about = None)]
+#[command(
+    version,
+    about,
+    long_about = None,
+    subcommand_negates_reqs = true,
+    override_usage = "anytest [OPTIONS] [PATHS]...\n       anytest [OPTIONS] <COMMAND>"
+)]
 pub struct Args {
-    #[arg()]
-    /// Path to the test file, can be a file (e.g. `path/to/file.rs`) or a file with a line number (e.g. `path/to/file.rs:123`)
-    path: String,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(required_unless_present_any = ["last", "changed"])]
+    /// Path to the test file, can be a file (e.g. `path/to/file.rs`) or a file with a line number (e.g. `path/to/file.rs:123`).
+    /// Several paths are run with as few commands as possible
+    paths: Vec<String>,
 
     /// Specify what tests to run
     #[arg(short, long)]
     scope: Option<Scope>,
 
     /// Path to the root directory, if not passed, the current directory is used
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     root: Option<String>,
 
+    /// Test framework to use (e.g. `rspec`), if not passed, it is detected from the path
+    #[arg(short, long, global = true)]
+    framework: Option<String>,
+
+    /// Run a single case of a parameterized test, either by its index or its id
+    #[arg(long)]
+    case: Option<String>,
+
+    /// Run the last executed test again
+    #[arg(long, default_value_t = false, conflicts_with_all = ["paths", "scope", "framework", "case"])]
+    last: bool,
+
+    /// Run the tests affected by the changes in the git repository, the tests of the changed
+    /// source files are found following the common conventions (e.g. `spec/foo_spec.rb` for `lib/foo.rb`)
+    #[arg(long, default_value_t = false, conflicts_with_all = ["paths", "scope", "framework", "case", "last"])]
+    changed: bool,
+
+    /// Git ref the changes are compared to, `HEAD` by default
+    #[arg(long, requires = "changed")]
+    base: Option<String>,
+
+    /// Fail instead of running the last test when the path can't be run (e.g. a non-test file)
+    #[arg(long, default_value_t = false, conflicts_with_all = ["last", "changed"])]
+    no_fallback: bool,
+
     /// Whether to run in dry-run mode
     #[arg(long, default_value_t = false)]
     dry_run: bool,
+
+    /// Output format, `json` prints the command details instead of running it
+    #[arg(long, value_enum, default_value_t = Format::Text, global = true)]
+    format: Format,
+
+    /// Shell the printed commands are escaped for
+    #[arg(long, value_enum, default_value_t = Shell::default(), global = true)]
+    shell: Shell,
+
+    /// Run the test again every time a file in the root directory changes
+    #[arg(short, long, default_value_t = false, conflicts_with_all = ["dry_run", "format"])]
+    watch: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// List the tests in a file along with the commands that run them
+    List {
+        /// Path to the test file
+        path: String,
+    },
+    /// Print the counterpart of a file, i.e. the test file of a source file or the other way around
+    Alt {
+        /// Path to the source or the test file
+        path: String,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Format {
+    Text,
+    Json,
 }
 
+/// The errors of the CLI itself, which get their own exit codes like the library errors.
+#[derive(Debug)]
+pub enum CliError {
+    /// The path can't be split into the file and the line, or isn't valid UTF-8.
+    InvalidPath(String),
+    /// The state file can't be located (e.g. without a home directory).
+    NoStateFile,
+    /// No test was run in the root yet (`--last`).
+    NoLastTest,
+    /// None of the changed files has tests (`--changed`).
+    NoChangedTests,
+    /// Only a single path can be watched (`--watch`).
+    WatchSeveralPaths,
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPath(path) => write!(f, "Invalid path {}", path),
+            Self::NoStateFile => f.write_str("Cannot determine the state file location"),
+            Self::NoLastTest => f.write_str("No last test found"),
+            Self::NoChangedTests => f.write_str("No tests affected by the changes found"),
+            Self::WatchSeveralPaths => f.write_str("Watching several paths isn't supported"),
+        }
+    }
+}
+
+impl Error for CliError {}
+
 impl Args {
-    pub fn to_context(&self) -> Result<Context, Box<dyn Error>> {
+    /// Parses the arguments of the process. The global options may precede the subcommand, so
+    /// clap can't tell the options of running the tests apart and they are checked here.
+    pub fn parse_checked() -> Self {
+        let args = Self::parse();
+
+        if let Err(message) = args.check_subcommand() {
+            <Self as CommandFactory>::command()
+                .error(ErrorKind::ArgumentConflict, message)
+                .exit();
+        }
+
+        args
+    }
+
+    fn check_subcommand(&self) -> Result<(), String> {
+        let Some(command) = &self.command else {
+            return Ok(());
+        };
+        let name = match command {
+            Command::List { .. } => "list",
+            Command::Alt { .. } => "alt",
+        };
+        let conflicts = [
+            ("[PATHS]...", !self.paths.is_empty()),
+            ("--scope", self.scope.is_some()),
+            ("--case", self.case.is_some()),
+            ("--last", self.last),
+            ("--changed", self.changed),
+            ("--base", self.base.is_some()),
+            ("--no-fallback", self.no_fallback),
+            ("--dry-run", self.dry_run),
+            ("--watch", self.watch),
+        ];
+
+        match conflicts.into_iter().find(|(_, is_present)| *is_present) {
+            Some((arg, _)) => Err(format!(
+                "the argument '{}' cannot be used with the '{}' subcommand",
+                arg, name
+            )),
+            None => Ok(()),
+        }
+    }
+
+    pub fn command(&self) -> Option<&Command> {
+        self.command.as_ref()
+    }
+
+    pub fn to_contexts(&self) -> Result<Vec<Context>, Box<dyn Error>> {
+        if self.last {
+            let root = env::current_dir()?.join(self.root.as_deref().unwrap_or_default());
+
+            return Ok(vec![state::load_last(&root)?]);
+        }
+
+        if self.changed {
+            let contexts = anytest::changed_contexts(self.root.as_deref(), self.base.as_deref())?;
+
+            if contexts.is_empty() {
+                return Err(CliError::NoChangedTests.into());
+            }
+
+            return Ok(contexts);
+        }
+
+        let contexts = self
+            .paths
+            .iter()
+            .map(|path| self.build_context(path))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let [context] = &contexts[..] {
+            if let Some(last) = self.fallback(context)? {
+                return Ok(vec![last]);
+            }
+        }
+
+        Ok(contexts)
+    }
+
+    /// The last test in the root directory, used when the path can't be run, e.g. when an editor
+    /// runs the test line from a non-test file.
+    fn fallback(&self, context: &Context) -> Result<Option<Context>, Box<dyn Error>> {
+        if self.no_fallback || context.framework().is_some() || anytest::is_runnable(context)? {
+            return Ok(None);
+        }
+
+        match state::load_last(context.root()) {
+            Ok(last) => {
+                let position = match last.scope() {
+                    Scope::Line => last.rel_full(),
+                    _ => last.rel_str().to_string(),
+                };
+                eprintln!(
+                    "No test framework found for {}, running the last test {}",
+                    context.rel_str(),
+                    position
+                );
+
+                Ok(Some(last))
+            }
+            Err(error) => {
+                log::debug!("{}", error);
+                Ok(None)
+            }
+        }
+    }
+
+    pub fn build_context(&self, path: &str) -> Result<Context, Box<dyn Error>> {
         let re = Regex::new(PATH_REGEX)?;
-        let caps = re.captures(&self.path).ok_or("Invalid path")?;
-        let path = caps.get(1).ok_or("Invalid path")?.as_str();
+        let invalid_path = || CliError::InvalidPath(path.to_string());
+        let caps = re.captures(path).ok_or_else(invalid_path)?;
+        let path = caps.get(1).ok_or_else(invalid_path)?.as_str();
         let line_nr = caps
             .get(2)
             .map(|m| m.as_str().parse::<LineNr>())
             .transpose()
             .unwrap_or(None);
 
-        Context::new(self.root.as_deref(), path, line_nr, self.scope.clone())
+        let context = Context::new(
+            self.root.as_deref(),
+            path,
+            line_nr,
+            self.scope.clone(),
+            self.framework.as_deref(),
+        )?;
+
+        Ok(context.with_case(self.case.as_deref()))
     }
 
     pub fn is_dry_run(&self) -> bool {
         self.dry_run
     }
+
+    pub fn is_watch(&self) -> bool {
+        self.watch
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn shell(&self) -> Shell {
+        self.shell
+    }
 }
 
 #[cfg(test)]
@@ -50,7 +275,7 @@ mod tests {
     use std::{
         env,
         fs::{self, File},
-        path::PathBuf,
+        path::{Path, PathBuf},
     };
 
     use super::*;
@@ -72,21 +297,58 @@ mod tests {
         (folder, file)
     }
 
-    fn build_args(root: &PathBuf, path: &str) -> Args {
+    fn build_args(root: &Path, path: &str) -> Args {
         Args {
-            path: path.to_string(),
+            command: None,
+            paths: vec![path.to_string()],
             scope: None,
             root: Some(root.to_str().unwrap().to_string()),
+            framework: None,
+            case: None,
+            last: false,
+            changed: false,
+            base: None,
+            no_fallback: false,
             dry_run: false,
+            format: Format::Text,
+            shell: Shell::Posix,
+            watch: false,
         }
     }
 
+    #[test]
+    fn test_args_global_option_before_subcommand() {
+        let args = Args::try_parse_from(["anytest", "--format", "json", "list", "normal_spec.rb"])
+            .unwrap();
+
+        assert!(matches!(args.command(), Some(Command::List { path }) if path == "normal_spec.rb"));
+        assert!(matches!(args.format(), Format::Json));
+        assert!(args.paths.is_empty());
+        assert!(args.check_subcommand().is_ok());
+
+        let args = Args::try_parse_from(["anytest", "-r", "tests", "alt", "lib/foo.rb"]).unwrap();
+
+        assert!(matches!(args.command(), Some(Command::Alt { path }) if path == "lib/foo.rb"));
+        assert_eq!(args.root.as_deref(), Some("tests"));
+    }
+
+    #[test]
+    fn test_args_subcommand_conflict() {
+        let args =
+            Args::try_parse_from(["anytest", "--dry-run", "list", "normal_spec.rb"]).unwrap();
+
+        assert_eq!(
+            args.check_subcommand().unwrap_err(),
+            "the argument '--dry-run' cannot be used with the 'list' subcommand"
+        );
+    }
+
     #[test]
     fn test_args_to_context() {
         let (folder, file) = init("folder", "test.rs");
 
         let args = build_args(&folder, "test.rs:123");
-        let context = args.to_context().unwrap();
+        let context = args.to_contexts().unwrap().remove(0);
 
         assert_eq!(context.root(), &folder);
         assert_eq!(context.path(), &file);
@@ -95,7 +357,7 @@ mod tests {
         assert!(matches!(context.scope(), &Scope::Line));
 
         let args = build_args(&folder, "test.rs:");
-        let context = args.to_context().unwrap();
+        let context = args.to_contexts().unwrap().remove(0);
 
         assert_eq!(context.root(), &folder);
         assert_eq!(context.path(), &file);
@@ -104,7 +366,7 @@ mod tests {
         assert!(matches!(context.scope(), &Scope::File));
 
         let args = build_args(&folder, "test.rs");
-        let context = args.to_context().unwrap();
+        let context = args.to_contexts().unwrap().remove(0);
 
         assert_eq!(context.root(), &folder);
         assert_eq!(context.path(), &file);
@@ -0,0 +1,230 @@
+use crate::{alternate::Projection, registry::Registry, Error};
+use regex::Regex;
+use serde::Deserialize;
+use std::{collections::HashMap, env, fs, path::Path, path::PathBuf};
+
+const PROJECT_FILE: &str = ".anytest.toml";
+const GLOBAL_DIR: &str = "anytest";
+const GLOBAL_FILE: &str = "config.toml";
+
+/// Overrides for a single test framework, all fields are optional.
+#[derive(Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FrameworkConfig {
+    pub pattern: Option<String>,
+    pub executable: Option<Vec<String>>,
+    pub args: Option<Vec<String>>,
+    pub test_pattern: Option<String>,
+    pub namespace_pattern: Option<String>,
+    /// `false` leaves the test framework out of the detection, `true` opts in to the ones that
+    /// aren't detected automatically (e.g. `nextest`).
+    pub enabled: Option<bool>,
+    /// Maps the source files to the test files, tried before the conventions.
+    pub projections: Option<Vec<Projection>>,
+}
+
+/// The configuration file, keyed by the test framework identifier (e.g. `rspec`, `jest`).
+#[derive(Deserialize, Default, Debug)]
+pub struct Config {
+    #[serde(flatten)]
+    frameworks: HashMap<String, FrameworkConfig>,
+}
+
+impl Config {
+    /// Looks for `.anytest.toml` in the root directory first, then for
+    /// `$XDG_CONFIG_HOME/anytest/config.toml` (`~/.config/anytest/config.toml` by default).
+    /// The first file found is used.
+    pub fn discover(root: &Path) -> Result<Self, Error> {
+        let candidates = [Some(root.join(PROJECT_FILE)), global_path()];
+
+        for path in candidates.into_iter().flatten() {
+            if path.is_file() {
+                return Self::load(&path);
+            }
+        }
+
+        Ok(Self::default())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path)?;
+        let config: Self = content
+            .parse()
+            .map_err(|error: toml::de::Error| Error::Config {
+                path: path.to_path_buf(),
+                message: error.to_string(),
+            })?;
+
+        config
+            .check_names()
+            .and_then(|_| config.check_patterns())
+            .map_err(|message| Error::Config {
+                path: path.to_path_buf(),
+                message,
+            })?;
+
+        Ok(config)
+    }
+
+    /// Checks that the sections are named after the test frameworks, so a typo (e.g. `[rpsec]`)
+    /// isn't silently ignored.
+    fn check_names(&self) -> Result<(), String> {
+        let registry = Registry::new(&Self::default());
+        let available = registry.names();
+        let mut names: Vec<_> = self.frameworks.keys().collect();
+        names.sort();
+
+        match names
+            .into_iter()
+            .find(|name| !available.contains(&name.as_str()))
+        {
+            Some(name) => Err(format!(
+                "unknown test framework `{}`, available test frameworks: {}",
+                name,
+                available.join(", ")
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that the patterns are valid regexes, so an invalid one is reported along with
+    /// the file instead of failing (or not matching) once the test framework uses it.
+    fn check_patterns(&self) -> Result<(), String> {
+        let mut names: Vec<_> = self.frameworks.keys().collect();
+        names.sort();
+
+        for name in names {
+            let framework = &self.frameworks[name];
+            let patterns = [
+                ("pattern", &framework.pattern),
+                ("test_pattern", &framework.test_pattern),
+                ("namespace_pattern", &framework.namespace_pattern),
+            ];
+
+            for (field, pattern) in patterns {
+                if let Some(pattern) = pattern {
+                    Regex::new(pattern)
+                        .map_err(|error| format!("`{}.{}` {}", name, field, error))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn framework(&self, name: &str) -> Option<&FrameworkConfig> {
+        self.frameworks.get(name)
+    }
+}
+
+impl std::str::FromStr for Config {
+    type Err = toml::de::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s)
+    }
+}
+
+fn global_path() -> Option<PathBuf> {
+    let dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(dir.join(GLOBAL_DIR).join(GLOBAL_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_parse() {
+        let config: Config = r#"
+            [rspec]
+            executable = ["bin/rspec"]
+            args = ["--require", "rails_helper"]
+
+            [jest]
+            pattern = '\.test\.ts$'
+
+            [nextest]
+            enabled = true
+
+            [[minitest.projections]]
+            source = "lib/**/*.rb"
+            test = "test/unit/**/*_test.rb"
+        "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            config.framework("rspec"),
+            Some(&FrameworkConfig {
+                executable: Some(vec!["bin/rspec".into()]),
+                args: Some(vec!["--require".into(), "rails_helper".into()]),
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            config.framework("jest").unwrap().pattern.as_deref(),
+            Some(r"\.test\.ts$")
+        );
+        assert_eq!(config.framework("nextest").unwrap().enabled, Some(true));
+        assert_eq!(
+            config.framework("minitest").unwrap().projections,
+            Some(vec![Projection::new(
+                "lib/**/*.rb",
+                "test/unit/**/*_test.rb"
+            )])
+        );
+        assert!(config.framework("pytest").is_none());
+    }
+
+    #[test]
+    fn test_config_parse_unknown_field() {
+        let error = "[rspec]\nexecutables = []".parse::<Config>().unwrap_err();
+
+        assert!(error.to_string().contains("unknown field `executables`"));
+    }
+
+    #[test]
+    fn test_config_load_invalid_pattern() {
+        let path = env::temp_dir().join(format!("anytest-config-{}.toml", std::process::id()));
+        fs::write(&path, "[jest]\ntest_pattern = '(unclosed'\n").unwrap();
+
+        let error = Config::load(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            error,
+            Error::Config { message, .. } if message.starts_with("`jest.test_pattern` regex")
+        ));
+    }
+
+    #[test]
+    fn test_config_load_unknown_framework() {
+        let path = env::temp_dir().join(format!("anytest-unknown-{}.toml", std::process::id()));
+        fs::write(&path, "[rpsec]\nargs = []\n").unwrap();
+
+        let error = Config::load(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            error,
+            Error::Config { message, .. }
+                if message.starts_with("unknown test framework `rpsec`")
+                    && message.contains("rspec")
+        ));
+    }
+
+    #[test]
+    fn test_config_discover() {
+        let config = Config::discover(Path::new("tests/fixtures/config")).unwrap();
+
+        assert_eq!(
+            config.framework("rspec").unwrap().executable,
+            Some(vec!["bin/rspec".into()])
+        );
+    }
+}
@@ -1,22 +1,39 @@
-use crate::{named_pattern::NamedPattern, LineNr, RelPath};
+use crate::{named_pattern::NamedPattern, rel_path::LineWithNr, Error, LineNr, RelPath};
 use clap::ValueEnum;
-use std::{error::Error, ops, path::PathBuf};
+use serde::{Deserialize, Serialize};
+use std::{iter, ops, path::PathBuf};
 
-#[derive(ValueEnum, Clone, Debug)]
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
 pub enum Scope {
     Suite,
     File,
     Line,
 }
 
+#[derive(Default)]
 pub struct Nearest {
     tests: Vec<String>,
     namespaces: Vec<String>,
     line_nr: Option<LineNr>,
-    // names: Vec<String>,
+    names: Vec<String>,
 }
 
 impl Nearest {
+    pub fn new(
+        tests: Vec<String>,
+        namespaces: Vec<String>,
+        line_nr: Option<LineNr>,
+        names: Vec<String>,
+    ) -> Self {
+        Self {
+            tests,
+            namespaces,
+            line_nr,
+            names,
+        }
+    }
+
     pub fn tests(&self) -> &[String] {
         &self.tests
     }
@@ -25,6 +42,11 @@ impl Nearest {
         &self.namespaces
     }
 
+    /// The names of the patterns that matched the tests.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
     pub fn line_nr(&self) -> Option<LineNr> {
         self.line_nr
     }
@@ -34,11 +56,15 @@ impl Nearest {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Context {
     rel_path: RelPath,
+    // The root passed by the user, it stays when the context is moved into a nested project
+    outer_root: PathBuf,
     line_nr: Option<LineNr>,
     scope: Scope,
+    framework: Option<String>,
+    case: Option<String>,
 }
 
 impl Context {
@@ -47,8 +73,18 @@ impl Context {
         path: &str,
         line_nr: Option<LineNr>,
         scope: Option<Scope>,
-    ) -> Result<Self, Box<dyn Error>> {
+        framework: Option<&str>,
+    ) -> Result<Self, Error> {
         let rel_path = RelPath::new(root, path)?;
+
+        // The lines are 1-based, the ones past the end of the file are caught when it is read
+        if line_nr == Some(0) {
+            return Err(Error::InvalidLine {
+                path: rel_path.path().clone(),
+                line_nr: 0,
+            });
+        }
+
         let scope = if let Some(scope) = scope {
             scope
         } else if line_nr.is_some() {
@@ -58,9 +94,12 @@ impl Context {
         };
 
         Ok(Self {
+            outer_root: rel_path.root().clone(),
             rel_path,
             line_nr,
             scope,
+            framework: framework.map(|s| s.to_string()),
+            case: None,
         })
     }
 
@@ -96,19 +135,77 @@ impl Context {
         &self.scope
     }
 
+    /// Returns the same context narrowed down to the passed line.
+    pub fn at_line(&self, line_nr: LineNr) -> Self {
+        Self {
+            line_nr: Some(line_nr),
+            scope: Scope::Line,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the context of another file in the same root (e.g. the test file of a source
+    /// file), as a whole.
+    pub(crate) fn for_file(&self, rel_path: &str) -> Result<Self, Error> {
+        Ok(Self {
+            rel_path: self.rel_path.file(rel_path)?,
+            outer_root: self.outer_root.clone(),
+            line_nr: None,
+            scope: Scope::File,
+            framework: None,
+            case: None,
+        })
+    }
+
+    /// Narrows a parameterized test down to a single case, either by its index or its id.
+    pub fn with_case(self, case: Option<&str>) -> Self {
+        Self {
+            case: case.map(|s| s.to_string()),
+            ..self
+        }
+    }
+
+    pub fn case(&self) -> Option<&str> {
+        self.case.as_deref()
+    }
+
+    /// Returns the same context relative to the nearest directory containing one of the markers
+    /// (e.g. `package.json`), so a project nested in the root runs on its own. The directories
+    /// above the root aren't searched, and the context is unchanged when none of them matches.
+    pub fn within_project(&self, markers: &[&str]) -> Self {
+        let project_root = self
+            .path()
+            .ancestors()
+            .take_while(|dir| dir.starts_with(self.root()))
+            .find(|dir| markers.iter().any(|marker| dir.join(marker).is_file()));
+
+        match project_root.and_then(|root| self.rel_path.with_root(root).ok()) {
+            Some(rel_path) => Self {
+                rel_path,
+                ..self.clone()
+            },
+            None => self.clone(),
+        }
+    }
+
+    /// The test framework identifier (e.g. `rspec`) forced by the user.
+    pub fn framework(&self) -> Option<&str> {
+        self.framework.as_deref()
+    }
+
     pub fn find_nearest(
         &self,
         test_patterns: &[NamedPattern],
         namespace_patters: &[NamedPattern],
         range: impl ops::RangeBounds<LineNr>,
-    ) -> Result<Nearest, Box<dyn Error>> {
+    ) -> Result<Nearest, Error> {
         if test_patterns.is_empty() {
-            return Err("Test patterns are empty".into());
+            return Err(Error::NoTestPatterns(self.path().clone()));
         }
 
         let mut tests: Vec<String> = Vec::new();
         let mut namespaces: Vec<String> = Vec::new();
-        // let names: Vec<String> = Vec::new();
+        let mut names: Vec<String> = Vec::new();
         let mut test_line_nr: Option<LineNr> = None;
         let mut last_namespace_line_nr: Option<LineNr> = None;
         let mut last_indent: Option<LineNr> = None;
@@ -120,7 +217,7 @@ impl Context {
                 .find_map(|pattern| pattern.find(&line));
             let indent = line.chars().take_while(|c| c.is_whitespace()).count();
 
-            if let Some((test_match, _)) = test_match {
+            if let Some((test_match, test_name)) = test_match {
                 if last_indent.is_none()
                     || (test_line_nr.is_none()
                         && last_indent.unwrap() > indent
@@ -134,9 +231,9 @@ impl Context {
                         }
                     }
                     tests.push(test_match);
-                    // if let Some(test_name) = test_name {
-                    //     names.push(test_name);
-                    // }
+                    if let Some(test_name) = test_name {
+                        names.push(test_name.to_string());
+                    }
                     last_indent = Some(indent);
                     test_line_nr = Some(number);
                 }
@@ -154,15 +251,33 @@ impl Context {
             tests,
             namespaces,
             line_nr: test_line_nr,
+            names,
         })
     }
 
+    pub fn lines(
+        &self,
+        range: impl ops::RangeBounds<LineNr>,
+    ) -> Result<Box<dyn Iterator<Item = LineWithNr>>, Error> {
+        self.rel_path.lines(range)
+    }
+
     pub fn find_file(&self, rel_path: &str) -> Option<RelPath> {
-        if let Ok(rel_path) = self.rel_path.file(rel_path) {
-            Some(rel_path)
-        } else {
-            None
-        }
+        self.rel_path.file(rel_path).ok()
+    }
+
+    /// Finds the file in the root or the directories above it, up to the root passed by the user
+    /// (e.g. the binaries hoisted to the root of a workspace). The path is relative to the root.
+    pub fn find_file_up(&self, rel_path: &str) -> Option<PathBuf> {
+        self.root()
+            .ancestors()
+            .take_while(|dir| dir.starts_with(&self.outer_root))
+            .position(|dir| dir.join(rel_path).is_file())
+            .map(|depth| {
+                iter::repeat_n("..", depth)
+                    .collect::<PathBuf>()
+                    .join(rel_path)
+            })
     }
 }
 
@@ -171,7 +286,8 @@ mod tests {
     use super::*;
 
     fn get_scope(line: Option<LineNr>, scope: Option<Scope>) -> Scope {
-        let context = Context::new(Some("tests/fixtures/folder"), "file.txt", line, scope).unwrap();
+        let context =
+            Context::new(Some("tests/fixtures/folder"), "file.txt", line, scope, None).unwrap();
         context.scope().clone()
     }
 
@@ -185,18 +301,33 @@ mod tests {
         assert!(matches!(get_scope(None, None), Scope::File));
     }
 
+    #[test]
+    fn test_context_new_invalid_line() {
+        let error = Context::new(
+            Some("tests/fixtures/folder"),
+            "file.txt",
+            Some(0),
+            None,
+            None,
+        )
+        .unwrap_err();
+
+        assert!(matches!(error, Error::InvalidLine { line_nr: 0, .. }));
+    }
+
     fn find_nearest(
         test_patterns: &[NamedPattern],
         namespace_patters: &[NamedPattern],
         range: impl ops::RangeBounds<LineNr>,
     ) -> Nearest {
-        Context::new(Some("tests/fixtures/folder"), "file.rb", None, None)
+        Context::new(Some("tests/fixtures/folder"), "file.rb", None, None, None)
             .unwrap()
             .find_nearest(test_patterns, namespace_patters, range)
             .unwrap()
     }
 
     #[test]
+    #[allow(clippy::reversed_empty_ranges)]
     fn test_content_find_nearest() {
         let nearest = find_nearest(
             &[r"^\s*def\s+(test_\w+)".into()],
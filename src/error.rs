@@ -0,0 +1,185 @@
+use crate::LineNr;
+use std::{
+    fmt, io,
+    path::{PathBuf, StripPrefixError},
+};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The errors of resolving the tests, so the callers can tell them apart (e.g. an editor can
+/// offer to pick the test framework when none is found) instead of matching the messages.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// No test framework can run the file. The candidates are the test frameworks matching the
+    /// file name, but not the project (e.g. Vitest without `vitest.config.ts`).
+    NoFramework {
+        path: PathBuf,
+        candidates: Vec<String>,
+    },
+    /// The test framework forced by the user doesn't exist.
+    UnknownFramework {
+        name: String,
+        available: Vec<String>,
+    },
+    PathNotFound(PathBuf),
+    /// The root path isn't an existing directory.
+    InvalidRoot(PathBuf),
+    PathOutsideRoot {
+        path: PathBuf,
+        root: PathBuf,
+    },
+    /// The line is out of the file.
+    InvalidLine {
+        path: PathBuf,
+        line_nr: LineNr,
+    },
+    /// There is no test at the line to narrow down, e.g. to one of its cases.
+    NoTestAtLine {
+        path: PathBuf,
+        line_nr: LineNr,
+    },
+    /// The test framework can't run a single case of a parameterized test, or can't pick it by
+    /// its index.
+    CaseNotSupported {
+        framework: String,
+    },
+    /// Finding the nearest test needs a line.
+    LineRequired(PathBuf),
+    /// The test framework has no test pattern (e.g. it is configured empty), so the tests in the
+    /// file can't be found.
+    NoTestPatterns(PathBuf),
+    /// Neither a source file nor a test file corresponds to the file.
+    NoAlternate(PathBuf),
+    /// The test framework has no test pattern to find the tests with.
+    ListingNotSupported {
+        framework: String,
+    },
+    /// The executable of the test framework is configured empty.
+    EmptyExecutable {
+        framework: String,
+    },
+    /// The program of the command isn't installed.
+    ExecutableNotFound(String),
+    /// A config file (`.anytest.toml` or a Cargo manifest) can't be parsed, or a pattern in it
+    /// isn't a valid regex.
+    Config {
+        path: PathBuf,
+        message: String,
+    },
+    /// A git command (e.g. for `--changed`) failed.
+    Git {
+        args: Vec<String>,
+        stderr: String,
+    },
+    /// The `--base` ref has no common ancestor with `HEAD`.
+    NoMergeBase(String),
+    /// The file can't be parsed to find the tests in it.
+    Parse(PathBuf),
+    Io(io::Error),
+    Other(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoFramework { path, candidates } if candidates.is_empty() => {
+                write!(f, "No suitable test framework found for {}", path.display())
+            }
+            Self::NoFramework { path, candidates } => write!(
+                f,
+                "No suitable test framework found for {}, the project isn't set up for {}",
+                path.display(),
+                candidates.join(", ")
+            ),
+            Self::UnknownFramework { name, available } => write!(
+                f,
+                "Unknown test framework `{}`, available test frameworks: {}",
+                name,
+                available.join(", ")
+            ),
+            Self::PathNotFound(path) => write!(f, "Path {} does not exist", path.display()),
+            Self::InvalidRoot(root) => write!(
+                f,
+                "Root path {} must be an existing directory",
+                root.display()
+            ),
+            Self::PathOutsideRoot { path, root } => write!(
+                f,
+                "Path {} must be a subpath of the root path {}",
+                path.display(),
+                root.display()
+            ),
+            Self::InvalidLine { path, line_nr } => {
+                write!(f, "Line #{} not found in {}", line_nr, path.display())
+            }
+            Self::NoTestAtLine { path, line_nr } => {
+                write!(f, "No test found at {}:{}", path.display(), line_nr)
+            }
+            Self::CaseNotSupported { framework } => {
+                write!(f, "{} can't run a single case of a test", framework)
+            }
+            Self::LineRequired(path) => {
+                write!(
+                    f,
+                    "Line number is required to find the test in {}",
+                    path.display()
+                )
+            }
+            Self::NoTestPatterns(path) => {
+                write!(
+                    f,
+                    "No test patterns to find the tests in {}",
+                    path.display()
+                )
+            }
+            Self::NoAlternate(path) => {
+                write!(f, "No alternate file found for {}", path.display())
+            }
+            Self::ListingNotSupported { framework } => {
+                write!(f, "Listing tests isn't supported by {}", framework)
+            }
+            Self::EmptyExecutable { framework } => {
+                write!(f, "The executable of {} is empty", framework)
+            }
+            Self::ExecutableNotFound(program) => write!(f, "Executable `{}` not found", program),
+            Self::Config { path, message } => {
+                write!(f, "Invalid config file {}: {}", path.display(), message)
+            }
+            Self::Git { args, stderr } => {
+                write!(f, "`git {}` failed: {}", args.join(" "), stderr)
+            }
+            Self::NoMergeBase(base) => write!(f, "No merge base found for `{}`", base),
+            Self::Parse(path) => write!(f, "Cannot parse {}", path.display()),
+            Self::Io(error) => error.fmt(f),
+            Self::Other(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<regex::Error> for Error {
+    fn from(error: regex::Error) -> Self {
+        Self::Other(error.to_string())
+    }
+}
+
+impl From<StripPrefixError> for Error {
+    fn from(error: StripPrefixError) -> Self {
+        Self::Other(error.to_string())
+    }
+}
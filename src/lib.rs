@@ -1,49 +1,278 @@
-use std::error::Error;
-use std::process::Command;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
 
+pub use changed::changed_contexts;
 pub use context::Context;
 pub use context::Scope;
+pub use error::{Error, Result};
 pub(crate) use rel_path::RelPath;
+pub use shell::Shell;
+pub use test_command::TestCommand;
+pub use test_item::TestItem;
 
 #[macro_use]
 extern crate anytest_derive;
 
+mod alternate;
+mod changed;
+mod config;
 mod context;
+mod error;
 mod language;
 mod named_pattern;
 mod registry;
 mod rel_path;
+mod shell;
+mod syntax;
+mod test_command;
 mod test_framework;
+mod test_item;
 mod utils;
 
 pub type LineNr = usize;
 pub(crate) type ArgsList = Vec<String>;
 
-pub fn build_command(context: &Context) -> Result<Command, Box<dyn Error>> {
-    let registry = registry::Registry::new();
-    let test_framework = registry.find(context)?;
-    let mut program_args = test_framework.executable(context).into_iter();
-    let program = program_args.next().ok_or("Program must be present")?;
+pub fn build_command(context: &Context) -> Result<Command, Error> {
+    resolve_command(context).map(TestCommand::into_command)
+}
+
+/// Builds the commands running all the contexts, e.g. the locations from a quickfix list.
+///
+/// The contexts are grouped by the project root and the test framework, and every group is
+/// run with as few commands as the test framework allows (e.g. `rspec a_spec.rb:1 b_spec.rb:2`).
+pub fn build_commands(contexts: &[Context]) -> Result<Vec<Command>, Error> {
+    let mut roots: Vec<&PathBuf> = vec![];
+
+    for context in contexts {
+        if !roots.contains(&context.root()) {
+            roots.push(context.root());
+        }
+    }
+
+    let mut commands = vec![];
+
+    for root in roots {
+        let config = config::Config::discover(root)?;
+        let registry = registry::Registry::new(&config);
+        let mut groups: Vec<(&dyn test_framework::TestFramework, ArgsList, Vec<Context>)> = vec![];
+
+        for context in contexts.iter().filter(|context| context.root() == root) {
+            let (test_framework, context) = find_test_framework(&registry, context)?;
+            // The executable may depend on the context (e.g. the scope), so it is a part of the group
+            let executable = test_framework.executable(&context);
+            let group = groups.iter_mut().find(|(other, other_executable, group)| {
+                other.name() == test_framework.name()
+                    && *other_executable == executable
+                    && group[0].root() == context.root()
+            });
+
+            match group {
+                Some((_, _, group)) => group.push(context),
+                None => groups.push((test_framework, executable, vec![context])),
+            }
+        }
+
+        for (test_framework, executable, group) in groups {
+            let group = group.iter().collect::<Vec<_>>();
+
+            for position_args in test_framework.merge_position_args(&group)? {
+                commands.push(assemble_command(
+                    test_framework,
+                    executable.clone(),
+                    group[0].root(),
+                    position_args,
+                )?);
+            }
+        }
+    }
+
+    Ok(commands)
+}
+
+/// Builds the command along with the test framework, the language and the nearest test
+/// it was resolved from.
+pub fn resolve_command(context: &Context) -> Result<TestCommand, Error> {
+    let config = config::Config::discover(context.root())?;
+    let registry = registry::Registry::new(&config);
+    let (test_framework, context) = find_test_framework(&registry, context)?;
+    let context = &context;
+    log::debug!(
+        "Using {} ({})",
+        test_framework.name(),
+        test_framework.language_name()
+    );
+
+    let command = build_framework_command(test_framework, context)?;
+    let test_command = TestCommand::new(
+        command,
+        test_framework.name(),
+        test_framework.language_name(),
+    );
+
+    if !matches!(context.scope(), Scope::Line) || test_framework.test_patterns().is_empty() {
+        return Ok(test_command);
+    }
+
+    let nearest = test_framework.find_nearest(context)?;
+    let test_name = test_framework.nearest_test_name(context, &nearest)?;
+
+    // Unlike the line, the case can't fall back to running the whole file, it needs a test or
+    // a namespace (e.g. `describe.each`) to narrow down
+    if context.case().is_some() && !nearest.has_tests() && nearest.namespaces().is_empty() {
+        return Err(Error::NoTestAtLine {
+            path: context.path().clone(),
+            line_nr: context.line_nr_or_default(),
+        });
+    }
+
+    Ok(test_command.with_nearest(test_name, nearest.line_nr(), nearest.namespaces().to_vec()))
+}
+
+/// Checks whether a test framework can run the context, either directly or through the tests
+/// of a source file.
+pub fn is_runnable(context: &Context) -> Result<bool, Error> {
+    let config = config::Config::discover(context.root())?;
+    let registry = registry::Registry::new(&config);
+
+    Ok(find_test_framework(&registry, context).is_ok())
+}
+
+/// Finds the counterpart of the file, i.e. the source file of a test file or the test file of
+/// a source file, following the projections of the test frameworks.
+pub fn find_alternate(context: &Context) -> Result<PathBuf, Error> {
+    let config = config::Config::discover(context.root())?;
+    let registry = registry::Registry::new(&config);
+    let source = registry
+        .find(context)
+        .ok()
+        .and_then(|(test_framework, context)| alternate::find_source(test_framework, &context));
+    let alternate = source.or_else(|| alternate::find_tests(&registry, context).into_iter().next());
+
+    // The alternate may be relative to a nested project, the path is relative to the root
+    alternate
+        .map(|alternate| {
+            alternate
+                .path()
+                .strip_prefix(context.root())
+                .unwrap_or(alternate.rel())
+                .to_path_buf()
+        })
+        .ok_or_else(|| Error::NoAlternate(context.path().clone()))
+}
+
+/// Lists all the tests in the file along with the commands that run them.
+pub fn list_tests(context: &Context) -> Result<Vec<TestItem>, Error> {
+    let config = config::Config::discover(context.root())?;
+    let registry = registry::Registry::new(&config);
+    let (test_framework, context) = registry.find(context)?;
+    let test_patterns = test_framework.test_patterns();
+
+    if test_patterns.is_empty() {
+        return Err(Error::ListingNotSupported {
+            framework: test_framework.name().into(),
+        });
+    }
+
+    let mut tests = vec![];
+
+    for (line, line_nr) in context.lines(..)? {
+        if !test_patterns
+            .iter()
+            .any(|pattern| pattern.find(&line).is_some())
+        {
+            continue;
+        }
+
+        let line_context = context.at_line(line_nr);
+        let nearest = test_framework.find_nearest(&line_context)?;
+
+        if nearest.line_nr() != Some(line_nr) {
+            continue;
+        }
+
+        let Some(name) = test_framework.nearest_test_name(&line_context, &nearest)? else {
+            continue;
+        };
+        let command = build_framework_command(test_framework, &line_context)?;
+
+        tests.push(TestItem::new(
+            line_nr,
+            name,
+            nearest.namespaces().to_vec(),
+            command,
+        ));
+    }
+
+    Ok(tests)
+}
+
+/// Finds the test framework of the context along with the context relative to its project, a
+/// source file is replaced with its test file unless the test framework is forced.
+fn find_test_framework<'a>(
+    registry: &'a registry::Registry,
+    context: &Context,
+) -> Result<(&'a dyn test_framework::TestFramework, Context), Error> {
+    match registry.find(context) {
+        Ok(found) => Ok(found),
+        Err(error) if context.framework().is_none() => {
+            let Some(test) = alternate::find_tests(registry, context).into_iter().next() else {
+                return Err(error);
+            };
+            log::debug!("Running {} for {}", test.rel_str(), context.rel_str());
+
+            registry.find(&test)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+fn build_framework_command(
+    test_framework: &dyn test_framework::TestFramework,
+    context: &Context,
+) -> Result<Command, Error> {
+    assemble_command(
+        test_framework,
+        test_framework.executable(context),
+        context.root(),
+        test_framework.position_args(context)?,
+    )
+}
+
+fn assemble_command(
+    test_framework: &dyn test_framework::TestFramework,
+    executable: ArgsList,
+    root: &Path,
+    position_args: ArgsList,
+) -> Result<Command, Error> {
+    let mut program_args = executable.into_iter();
+    let program = program_args.next().ok_or_else(|| Error::EmptyExecutable {
+        framework: test_framework.name().into(),
+    })?;
     let mut command = Command::new(program);
 
-    command.current_dir(context.root());
+    command.current_dir(root);
     command.args(program_args);
     command.args(test_framework.args());
-    command.args(test_framework.position_args(context)?);
+    command.args(position_args);
 
     Ok(command)
 }
 
+/// Runs the command with the standard streams inherited and waits for it to exit, a program
+/// that isn't installed fails with [`Error::ExecutableNotFound`].
+pub fn run_command(command: &mut Command) -> Result<ExitStatus, Error> {
+    command.status().map_err(|error| match error.kind() {
+        io::ErrorKind::NotFound => {
+            Error::ExecutableNotFound(command.get_program().to_string_lossy().into())
+        }
+        _ => error.into(),
+    })
+}
+
+/// Renders the command as a POSIX shell string that can be pasted verbatim.
 pub fn format_command(command: &Command) -> String {
-    format!(
-        "{} {}",
-        command.get_program().to_str().unwrap_or_default(),
-        command
-            .get_args()
-            .map(|a| a.to_str().unwrap_or_default())
-            .collect::<Vec<&str>>()
-            .join(" ")
-    )
+    Shell::Posix.format(command)
 }
 
 #[cfg(test)]
@@ -55,6 +284,16 @@ mod tests {
         let mut command = Command::new("echo");
         command.arg("Hello,").arg("World!");
 
-        assert_eq!(format_command(&command), "echo Hello, World!");
+        assert_eq!(format_command(&command), "echo 'Hello,' 'World!'");
+    }
+
+    #[test]
+    fn test_run_command_not_found() {
+        let error = run_command(&mut Command::new("anytest-missing-program")).unwrap_err();
+
+        assert!(matches!(
+            error,
+            Error::ExecutableNotFound(program) if program == "anytest-missing-program"
+        ));
     }
 }
@@ -1,29 +1,139 @@
-use clap::Parser;
-use cli::Args;
-use std::{error::Error, process::ExitCode};
+use anytest::Context;
+use cli::{Args, CliError, Command, Format};
+use std::{
+    error::Error,
+    process::{self, ExitCode},
+};
 
 mod cli;
+mod output;
+mod state;
+mod watch;
 
-fn main() -> Result<ExitCode, Box<dyn Error>> {
-    let args = Args::parse();
-    let context = args.to_context()?;
-    let mut command = anytest::build_command(&context)?;
+fn main() -> ExitCode {
+    match try_main() {
+        Ok(exit_code) => exit_code,
+        Err(error) => {
+            eprintln!("Error: {}", error);
 
-    if args.is_dry_run() {
-        println!("{}", anytest::format_command(&command));
-    } else {
-        let output = command.spawn()?.wait_with_output()?;
-
-        if !output.status.success() {
-            return Ok(output
-                .status
-                .code()
-                .unwrap_or(1)
-                .try_into()
-                .unwrap_or(1)
-                .into());
+            exit_code(error.as_ref()).into()
         }
     }
+}
+
+/// The exit codes of the errors, so the scripts and the editors can tell them apart from each
+/// other and from the failures of the tests, which keep the exit code of the test command.
+fn exit_code(error: &(dyn Error + 'static)) -> u8 {
+    if let Some(error) = error.downcast_ref::<CliError>() {
+        return match error {
+            CliError::InvalidPath(_) => 82,
+            CliError::NoStateFile | CliError::NoLastTest => 83,
+            CliError::NoChangedTests => 84,
+            CliError::WatchSeveralPaths => 85,
+        };
+    }
+
+    match error.downcast_ref::<anytest::Error>() {
+        Some(anytest::Error::NoFramework { .. }) => 64,
+        Some(anytest::Error::UnknownFramework { .. }) => 65,
+        Some(anytest::Error::PathNotFound(_)) => 66,
+        Some(anytest::Error::InvalidRoot(_)) => 67,
+        Some(anytest::Error::PathOutsideRoot { .. }) => 68,
+        Some(anytest::Error::InvalidLine { .. }) => 69,
+        Some(anytest::Error::NoTestAtLine { .. }) => 70,
+        Some(anytest::Error::CaseNotSupported { .. }) => 71,
+        Some(anytest::Error::LineRequired(_)) => 72,
+        Some(anytest::Error::NoTestPatterns(_)) => 73,
+        Some(anytest::Error::Io(_)) => 74,
+        Some(anytest::Error::NoAlternate(_)) => 75,
+        Some(anytest::Error::ListingNotSupported { .. }) => 76,
+        Some(anytest::Error::EmptyExecutable { .. }) => 77,
+        Some(anytest::Error::Config { .. }) => 78,
+        Some(anytest::Error::Git { .. }) => 79,
+        Some(anytest::Error::NoMergeBase(_)) => 80,
+        Some(anytest::Error::Parse(_)) => 81,
+        // Following the shells, e.g. `sh -c missing` exits with 127
+        Some(anytest::Error::ExecutableNotFound(_)) => 127,
+        _ => 2,
+    }
+}
+
+fn try_main() -> Result<ExitCode, Box<dyn Error>> {
+    let args = Args::parse_checked();
+
+    if let Some(Command::List { path }) = args.command() {
+        let context = args.build_context(path)?;
+        let tests = anytest::list_tests(&context)?;
+
+        output::print_tests(&context, &tests, args.format(), args.shell())?;
+
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(Command::Alt { path }) = args.command() {
+        let context = args.build_context(path)?;
+        let alternate = anytest::find_alternate(&context)?;
+
+        output::print_alternate(&alternate, args.format())?;
+
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut contexts = args.to_contexts()?;
+
+    if contexts.len() > 1 {
+        return run_many(&args, &contexts);
+    }
+
+    let context = contexts.remove(0);
+    let test_command = anytest::resolve_command(&context)?;
+
+    state::save_last(&context);
+
+    if args.is_watch() {
+        watch::run(&context, args.shell())?;
+    } else if args.is_dry_run() || matches!(args.format(), Format::Json) {
+        output::print_command(&context, &test_command, args.format(), args.shell())?;
+    } else {
+        return run(test_command.into_command());
+    }
 
     Ok(ExitCode::SUCCESS)
 }
+
+/// Runs several locations, the commands are run one by one and the first failure is reported.
+fn run_many(args: &Args, contexts: &[Context]) -> Result<ExitCode, Box<dyn Error>> {
+    if args.is_watch() {
+        return Err(CliError::WatchSeveralPaths.into());
+    }
+
+    let commands = anytest::build_commands(contexts)?;
+
+    if args.is_dry_run() || matches!(args.format(), Format::Json) {
+        output::print_commands(&commands, args.format(), args.shell())?;
+
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for command in commands {
+        let code = run(command)?;
+
+        if exit_code == ExitCode::SUCCESS {
+            exit_code = code;
+        }
+    }
+
+    Ok(exit_code)
+}
+
+fn run(mut command: process::Command) -> Result<ExitCode, Box<dyn Error>> {
+    let status = anytest::run_command(&mut command)?;
+
+    if status.success() {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(status.code().unwrap_or(1).try_into().unwrap_or(1).into())
+    }
+}
@@ -0,0 +1,140 @@
+use crate::cli::Format;
+use anytest::{Context, Scope, Shell, TestCommand, TestItem};
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, error::Error, path::Path, process::Command};
+
+fn command_json(command: &Command, shell: Shell) -> Value {
+    // No test framework sets variables yet, the object is still there so the schema doesn't
+    // change once one does (a removed variable is `null`)
+    let env = command
+        .get_envs()
+        .map(|(key, value)| {
+            (
+                key.to_string_lossy().to_string(),
+                value.map(|value| value.to_string_lossy().to_string()),
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    json!({
+        "program": command.get_program().to_string_lossy(),
+        "args": command
+            .get_args()
+            .map(|arg| arg.to_string_lossy())
+            .collect::<Vec<_>>(),
+        "cwd": command
+            .get_current_dir()
+            .map(|dir| dir.to_string_lossy()),
+        "env": env,
+        "command": shell.format(command),
+    })
+}
+
+pub fn print_command(
+    context: &Context,
+    test_command: &TestCommand,
+    format: Format,
+    shell: Shell,
+) -> Result<(), Box<dyn Error>> {
+    match format {
+        Format::Text => println!("{}", shell.format(test_command.command())),
+        Format::Json => {
+            let mut value = command_json(test_command.command(), shell);
+            let nearest = test_command.test_line_nr().map(|line_nr| {
+                json!({
+                    "line": line_nr,
+                    "name": test_command.test_name(),
+                    "namespaces": test_command.namespaces(),
+                })
+            });
+
+            value["framework"] = test_command.framework().into();
+            value["language"] = test_command.language().into();
+            value["scope"] = serde_json::to_value(context.scope())?;
+            value["path"] = context.rel_str().into();
+            value["line"] = context.line_nr().into();
+            value["case"] = context.case().into();
+            value["nearest"] = nearest.into();
+
+            println!("{}", serde_json::to_string_pretty(&value)?);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn print_commands(
+    commands: &[Command],
+    format: Format,
+    shell: Shell,
+) -> Result<(), Box<dyn Error>> {
+    match format {
+        Format::Text => {
+            for command in commands {
+                println!("{}", shell.format(command));
+            }
+        }
+        Format::Json => {
+            let value = commands
+                .iter()
+                .map(|command| command_json(command, shell))
+                .collect::<Vec<_>>();
+
+            println!("{}", serde_json::to_string_pretty(&value)?);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn print_alternate(path: &Path, format: Format) -> Result<(), Box<dyn Error>> {
+    match format {
+        Format::Text => println!("{}", path.display()),
+        Format::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({ "path": path.to_string_lossy() }))?
+        ),
+    }
+
+    Ok(())
+}
+
+pub fn print_tests(
+    context: &Context,
+    tests: &[TestItem],
+    format: Format,
+    shell: Shell,
+) -> Result<(), Box<dyn Error>> {
+    match format {
+        Format::Text => {
+            for test in tests {
+                println!(
+                    "{}:{} {}",
+                    context.rel_str(),
+                    test.line_nr(),
+                    test.full_name()
+                );
+                println!("    {}", shell.format(test.command()));
+            }
+        }
+        Format::Json => {
+            let tests = tests
+                .iter()
+                .map(|test| {
+                    let mut value = command_json(test.command(), shell);
+
+                    value["line"] = test.line_nr().into();
+                    value["name"] = test.name().into();
+                    value["namespaces"] = test.namespaces().into();
+                    value["scope"] = serde_json::to_value(Scope::Line)?;
+
+                    Ok(value)
+                })
+                .collect::<Result<Vec<_>, serde_json::Error>>()?;
+
+            println!("{}", serde_json::to_string_pretty(&tests)?);
+        }
+    }
+
+    Ok(())
+}
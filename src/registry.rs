@@ -1,6 +1,7 @@
-use crate::{test_framework::TestFramework, Context};
+use crate::{config::Config, test_framework::TestFramework, Context, Error};
 
 mod elixir;
+mod go;
 mod javascript;
 mod python;
 mod ruby;
@@ -9,20 +10,43 @@ mod zig;
 
 pub struct Registry {
     frameworks: Vec<Box<dyn TestFramework>>,
+    /// The test frameworks turned off with `enabled = false`, they run only when forced.
+    disabled: Vec<String>,
 }
 
 impl Registry {
-    pub fn new() -> Self {
-        let mut registry = Self { frameworks: vec![] };
+    pub fn new(config: &Config) -> Self {
+        let mut registry = Self {
+            frameworks: vec![],
+            disabled: vec![],
+        };
 
         registry.add(Box::<elixir::ESpec>::default());
         registry.add(Box::<elixir::ExUnit>::default());
+        registry.add(Box::<go::GoTest>::default());
+        registry.add(Box::<javascript::Vitest>::default());
+        registry.add(Box::<javascript::Mocha>::default());
         registry.add(Box::<javascript::Jest>::default());
+        registry.add(Box::<python::DjangoTest>::default());
+        registry.add(Box::<python::Nose2>::default());
+        registry.add(Box::<python::PyUnit>::default());
         registry.add(Box::<python::Pytest>::default());
         registry.add(Box::<ruby::RSpec>::default());
+        registry.add(Box::<ruby::Minitest>::default());
+        registry.add(Box::<rust::Nextest>::default());
         registry.add(Box::<rust::Cargotest>::default());
         registry.add(Box::<zig::Zigtest>::default());
 
+        for framework in registry.frameworks.iter_mut() {
+            if let Some(framework_config) = config.framework(framework.name()) {
+                framework.configure(framework_config);
+
+                if framework_config.enabled == Some(false) {
+                    registry.disabled.push(framework.name().to_string());
+                }
+            }
+        }
+
         registry
     }
 
@@ -30,16 +54,91 @@ impl Registry {
         self.frameworks.push(framework);
     }
 
-    pub fn find(
-        &self,
-        context: &Context,
-    ) -> Result<&dyn TestFramework, Box<dyn std::error::Error>> {
-        for framework in &self.frameworks {
-            if framework.is_suitable_for(context) {
-                return Ok(framework.as_ref());
+    /// Finds the test framework of the context along with the context relative to the root of
+    /// the project it belongs to, see [`TestFramework::root_markers`].
+    pub fn find(&self, context: &Context) -> Result<(&dyn TestFramework, Context), Error> {
+        if let Some(name) = context.framework() {
+            let framework = self.get(name)?;
+
+            return Ok((framework, context.within_project(framework.root_markers())));
+        }
+
+        for framework in self.frameworks() {
+            let context = context.within_project(framework.root_markers());
+
+            if framework.is_suitable_for(&context) {
+                return Ok((framework, context));
             }
         }
 
-        Err("No suitable test framework found".into())
+        Err(Error::NoFramework {
+            path: context.path().clone(),
+            candidates: self
+                .frameworks()
+                .filter(|framework| framework.matches_pattern(context))
+                .map(|framework| framework.name().to_string())
+                .collect(),
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Result<&dyn TestFramework, Error> {
+        self.frameworks
+            .iter()
+            .find(|framework| framework.name() == name)
+            .map(|framework| framework.as_ref())
+            .ok_or_else(|| Error::UnknownFramework {
+                name: name.to_string(),
+                available: self.names().iter().map(|name| name.to_string()).collect(),
+            })
+    }
+
+    /// The test frameworks that are detected automatically, i.e. all but the disabled ones.
+    pub fn frameworks(&self) -> impl Iterator<Item = &dyn TestFramework> {
+        self.frameworks
+            .iter()
+            .map(|framework| framework.as_ref())
+            .filter(|framework| !self.disabled.iter().any(|name| name == framework.name()))
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.frameworks
+            .iter()
+            .map(|framework| framework.name())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(config: &str, framework: Option<&str>) -> Result<String, Error> {
+        let registry = Registry::new(&config.parse().unwrap());
+        let context = Context::new(
+            Some("tests/fixtures/jest"),
+            "__tests__/normal-test.js",
+            None,
+            None,
+            framework,
+        )
+        .unwrap();
+
+        registry
+            .find(&context)
+            .map(|(framework, _)| framework.name().to_string())
+    }
+
+    #[test]
+    fn test_find_disabled() {
+        assert_eq!(find("", None).unwrap(), "jest");
+        assert!(matches!(
+            find("[jest]\nenabled = false", None),
+            Err(Error::NoFramework { candidates, .. }) if !candidates.contains(&"jest".into())
+        ));
+        assert_eq!(
+            find("[jest]\nenabled = false", Some("jest")).unwrap(),
+            "jest"
+        );
+        assert_eq!(find("[jest]\nenabled = true", None).unwrap(), "jest");
     }
 }
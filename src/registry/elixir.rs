@@ -9,3 +9,6 @@ mod exunit;
 
 #[derive(Language, Default)]
 struct Elixir {}
+
+/// A Mix project, its tests run from the directory of `mix.exs`.
+const ROOT_MARKERS: [&str; 1] = ["mix.exs"];
@@ -1,5 +1,9 @@
 use super::Elixir;
-use crate::test_framework::{TestFramework, TestFrameworkMeta};
+use crate::{
+    alternate::Projection,
+    test_framework::{self, TestFramework, TestFrameworkMeta},
+    ArgsList, Context, Error,
+};
 use smart_default::SmartDefault;
 
 #[derive(TestFrameworkMeta, SmartDefault)]
@@ -7,11 +11,21 @@ pub struct ESpec {
     language: Elixir,
     #[default = r"_spec\.exs$"]
     pattern: String,
-    #[default(_code = r#"vec!["mix", "espec"]"#)]
-    executable: Vec<&'static str>,
-    args: Vec<&'static str>,
+    #[default(_code = r#"vec!["mix".into(), "espec".into()]"#)]
+    executable: Vec<String>,
+    args: Vec<String>,
     test_pattern: String,
     namespace_pattern: String,
+    #[default(_code = r#"vec![Projection::new("lib/**/*.ex", "spec/**/*_spec.exs")]"#)]
+    projections: Vec<Projection>,
 }
 
-impl TestFramework for ESpec {}
+impl TestFramework for ESpec {
+    fn root_markers(&self) -> &[&str] {
+        &super::ROOT_MARKERS
+    }
+
+    fn merge_position_args(&self, contexts: &[&Context]) -> Result<Vec<ArgsList>, Error> {
+        test_framework::concat_position_args(self, contexts)
+    }
+}
@@ -1,9 +1,10 @@
-use std::error::Error;
+use std::fs;
 
 use super::Elixir;
 use crate::{
-    test_framework::{TestFramework, TestFrameworkMeta},
-    ArgsList, Context,
+    alternate::Projection,
+    test_framework::{self, TestFramework, TestFrameworkMeta},
+    ArgsList, Context, Error,
 };
 use smart_default::SmartDefault;
 
@@ -12,10 +13,14 @@ pub struct ExUnit {
     language: Elixir,
     #[default = r"_test\.exs$"]
     pattern: String,
-    executable: Vec<&'static str>,
-    args: Vec<&'static str>,
+    executable: Vec<String>,
+    args: Vec<String>,
+    #[default = r#"^\s*test\s+"(.*?)""#]
     test_pattern: String,
+    #[default = r#"^\s*describe\s+"(.*?)""#]
     namespace_pattern: String,
+    #[default(_code = r#"vec![Projection::new("lib/**/*.ex", "test/**/*_test.exs")]"#)]
+    projections: Vec<Projection>,
 }
 
 // TODO: cache this method
@@ -24,6 +29,10 @@ fn is_mix(context: &Context) -> bool {
 }
 
 impl TestFramework for ExUnit {
+    fn root_markers(&self) -> &[&str] {
+        &super::ROOT_MARKERS
+    }
+
     fn build_executable(&self, context: &Context) -> ArgsList {
         if is_mix(context) {
             vec!["mix".into(), "test".into()]
@@ -32,15 +41,27 @@ impl TestFramework for ExUnit {
         }
     }
 
-    fn build_suite_position_args(&self, context: &Context) -> Result<ArgsList, Box<dyn Error>> {
+    fn build_suite_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
         if is_mix(context) {
-            Ok(vec![])
-        } else {
-            Ok(vec!["*.exs".into()])
+            return Ok(vec![]);
         }
+
+        // `elixir` runs a single script and passes the rest to it as arguments, so all the
+        // `*.exs` files are required instead and ExUnit runs their tests when they are loaded
+        let mut files = fs::read_dir(context.root())?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "exs"))
+            .filter_map(|path| path.file_name()?.to_str().map(|name| name.to_string()))
+            .collect::<ArgsList>();
+
+        files.sort();
+        Ok(files
+            .into_iter()
+            .flat_map(|file| ["-r".to_string(), file])
+            .collect())
     }
 
-    fn build_line_position_args(&self, context: &Context) -> Result<ArgsList, Box<dyn Error>> {
+    fn build_line_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
         let file_args = self.build_file_position_args(context)?;
 
         if !is_mix(context) || context.line_nr_or_default() < 2 {
@@ -49,4 +70,17 @@ impl TestFramework for ExUnit {
 
         Ok(vec![context.rel_full()])
     }
+
+    fn merge_position_args(&self, contexts: &[&Context]) -> Result<Vec<ArgsList>, Error> {
+        // `elixir` runs a single script, the rest are passed to it as arguments
+        match contexts.first() {
+            Some(context) if is_mix(context) => {
+                test_framework::concat_position_args(self, contexts)
+            }
+            _ => contexts
+                .iter()
+                .map(|context| self.position_args(context))
+                .collect(),
+        }
+    }
 }
@@ -0,0 +1,12 @@
+use crate::language::Language;
+
+// Test frameworks
+pub use gotest::GoTest;
+
+mod gotest;
+
+#[derive(Language, Default)]
+struct Go {}
+
+/// A Go module, its packages are relative to the directory of `go.mod`.
+const ROOT_MARKERS: [&str; 1] = ["go.mod"];
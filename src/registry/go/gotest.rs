@@ -0,0 +1,153 @@
+use super::Go;
+use crate::{
+    alternate::Projection,
+    context::Nearest,
+    named_pattern::NamedPattern,
+    test_framework::{TestFramework, TestFrameworkMeta},
+    utils, ArgsList, Context, Error,
+};
+use regex::Regex;
+use smart_default::SmartDefault;
+use std::path::Component;
+
+const SUBTEST: &str = "subtest";
+
+#[derive(TestFrameworkMeta, SmartDefault)]
+pub struct GoTest {
+    language: Go,
+    #[default = r"_test\.go$"]
+    pattern: String,
+    #[default(_code = r#"vec!["go".into(), "test".into()]"#)]
+    executable: Vec<String>,
+    args: Vec<String>,
+    #[default = r"^func\s+((?:Test|Benchmark|Example|Fuzz)\w*)\s*\("]
+    test_pattern: String,
+    namespace_pattern: String,
+    #[default(_code = r#"vec![Projection::new("**/*.go", "**/*_test.go")]"#)]
+    projections: Vec<Projection>,
+
+    // `t.Run("name", ...)` subtests
+    #[default = r#"^\s*\w+\.Run\(\s*"(.*?)""#]
+    subtest_pattern: String,
+}
+
+impl GoTest {
+    /// The package directory of the file, e.g. `./pkg/foo`.
+    fn package(&self, context: &Context) -> String {
+        let dirs = context
+            .rel()
+            .parent()
+            .into_iter()
+            .flat_map(|parent| parent.components())
+            .filter_map(|component| match component {
+                Component::Normal(dir) => dir.to_str(),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if dirs.is_empty() {
+            ".".into()
+        } else {
+            format!("./{}", dirs.join("/"))
+        }
+    }
+}
+
+impl TestFramework for GoTest {
+    fn root_markers(&self) -> &[&str] {
+        &super::ROOT_MARKERS
+    }
+
+    fn test_patterns(&self) -> Vec<NamedPattern> {
+        vec![
+            self.test_pattern().into(),
+            NamedPattern::new(&self.subtest_pattern, Some(SUBTEST)),
+        ]
+    }
+
+    fn build_suite_position_args(&self, _context: &Context) -> Result<ArgsList, Error> {
+        Ok(vec!["./...".into()])
+    }
+
+    fn build_file_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
+        Ok(vec![self.package(context)])
+    }
+
+    fn build_line_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
+        let args = self.build_file_position_args(context)?;
+        let nearest = self.find_nearest(context)?;
+        let names = [nearest.namespaces(), nearest.tests()].concat();
+        let Some(function) = names.first() else {
+            return Ok(args);
+        };
+        // `go test` replaces spaces in subtest names with underscores
+        let filter = names
+            .iter()
+            .map(|name| format!("^{}$", regex::escape(&name.replace(' ', "_"))))
+            .collect::<Vec<_>>()
+            .join("/");
+
+        if function.starts_with("Benchmark") {
+            // `-run` matches all the tests by default, so they are skipped to run the benchmark only
+            Ok(utils::concat(args, ["-run", "^$", "-bench", &filter]))
+        } else {
+            Ok(utils::concat(args, ["-run".to_string(), filter]))
+        }
+    }
+
+    /// Walks up from the line collecting the enclosing subtests until the test function is
+    /// reached, so the function ends up in the namespaces and the innermost subtest in the tests.
+    fn find_nearest(&self, context: &Context) -> Result<Nearest, Error> {
+        let line_nr = context
+            .line_nr()
+            .ok_or_else(|| Error::LineRequired(context.path().clone()))?;
+        let function_pattern = Regex::new(self.test_pattern())?;
+        let subtest_pattern = Regex::new(&self.subtest_pattern)?;
+        let any_function_pattern = Regex::new(r"^func\s")?;
+        let mut names: Vec<String> = vec![];
+        let mut test_line_nr = None;
+        let mut min_indent: Option<usize> = None;
+
+        for (line, number) in context.lines(line_nr..=1)? {
+            if let Some(captures) = function_pattern.captures(&line) {
+                test_line_nr = test_line_nr.or(Some(number));
+                names.reverse();
+
+                // The innermost subtest (or the function itself) is the test
+                let mut namespaces = vec![captures[1].to_string()];
+                namespaces.extend(names);
+                let tests = namespaces.pop().into_iter().collect();
+                let pattern_names = if namespaces.is_empty() {
+                    vec![]
+                } else {
+                    vec![SUBTEST.to_string()]
+                };
+
+                return Ok(Nearest::new(tests, namespaces, test_line_nr, pattern_names));
+            }
+
+            // Either a helper function or the end of a function above the line
+            if any_function_pattern.is_match(&line) || (number < line_nr && line.starts_with('}')) {
+                break;
+            }
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            // Only the subtests enclosing the line are less indented than everything below them
+            let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+
+            if let Some(captures) = subtest_pattern.captures(&line) {
+                if min_indent.is_none_or(|min_indent| indent < min_indent) {
+                    names.push(captures[1].to_string());
+                    test_line_nr = test_line_nr.or(Some(number));
+                }
+            }
+
+            min_indent = Some(min_indent.map_or(indent, |min_indent| min_indent.min(indent)));
+        }
+
+        Ok(Nearest::default())
+    }
+}
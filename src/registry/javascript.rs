@@ -1,9 +1,363 @@
-use crate::language::Language;
+use crate::{
+    alternate::Projection, context::Nearest, language::Language, named_pattern::NamedPattern,
+    test_framework::TestFramework, utils, ArgsList, Context, Error, LineNr,
+};
+use regex::Regex;
+use std::fs;
 
 // Test frameworks
 pub use jest::Jest;
+pub use mocha::Mocha;
+pub use vitest::Vitest;
 
 mod jest;
+mod mocha;
+mod vitest;
+
+const PACKAGE_FILE: &str = "package.json";
+const CONFIG_EXTENSIONS: [&str; 6] = ["js", "ts", "mjs", "cjs", "mts", "cts"];
+const SOURCE_EXTENSIONS: [&str; 4] = ["js", "jsx", "ts", "tsx"];
+const TEST_PATTERN: &str = r#"^\s*(?:it|test)\s*[\( ]\s*["'\`](.*?)["'\`]"#;
+const NAMESPACE_PATTERN: &str = r#"^\s*(?:describe|suite|context)\s*[( ]\s*["'\`](.*?)["'\`]"#;
+// `it.each(table)("name", ...)` and the closing line of a multi-line table, e.g. `])("name", ...)`
+const EACH_TEST_PATTERN: &str =
+    r#"^\s*(?:(?:it|test)(?:\.\w+)*\.each\s*(?:\(.*\)|`.*`)|\]\)|`)\s*\(\s*["'`](.*?)["'`]"#;
+const EACH_NAMESPACE_PATTERN: &str =
+    r#"^\s*(?:describe|suite)(?:\.\w+)*\.each\s*(?:\(.*\)|`.*`)\s*\(\s*["'`](.*?)["'`]"#;
 
 #[derive(Language, Default)]
 struct JavaScript {}
+
+/// A package, its config is next to `package.json`.
+const ROOT_MARKERS: [&str; 1] = ["package.json"];
+
+/// The binary installed in the package, or hoisted to the root of the workspace.
+fn build_executable(context: &Context, bin: &str) -> ArgsList {
+    match context.find_file_up(&format!("node_modules/.bin/{}", bin)) {
+        Some(path) => vec![path.to_string_lossy().into_owned()],
+        None => vec![bin.to_string()],
+    }
+}
+
+/// The test files next to the source files (e.g. `src/a.test.ts`) or in `__tests__`.
+fn projections() -> Vec<Projection> {
+    let mut projections = vec![];
+
+    for ext in SOURCE_EXTENSIONS {
+        let source = format!("**/*.{}", ext);
+
+        projections.extend([
+            Projection::new(&source, &format!("**/*.test.{}", ext)),
+            Projection::new(&source, &format!("**/*.spec.{}", ext)),
+            Projection::new(&source, &format!("**/__tests__/*.{}", ext)),
+            Projection::new(&source, &format!("**/__tests__/*.test.{}", ext)),
+            Projection::new(
+                &format!("src/**/*.{}", ext),
+                &format!("__tests__/**/*.{}", ext),
+            ),
+        ]);
+    }
+
+    projections
+}
+
+/// The test files in the `test` directory mirroring the `src` or the `lib` one.
+fn mocha_projections() -> Vec<Projection> {
+    let mut projections = vec![];
+
+    for dir in ["src", "lib"] {
+        for ext in ["js", "ts"] {
+            let source = format!("{}/**/*.{}", dir, ext);
+
+            projections.extend([
+                Projection::new(&source, &format!("test/**/*.spec.{}", ext)),
+                Projection::new(&source, &format!("test/**/*.test.{}", ext)),
+                Projection::new(&source, &format!("test/**/*.{}", ext)),
+            ]);
+        }
+    }
+
+    projections
+}
+
+/// Checks whether `package.json` in the root directory lists the package as a dependency.
+fn has_dependency(context: &Context, name: &str) -> bool {
+    let Some(package) = context.find_file(PACKAGE_FILE) else {
+        return false;
+    };
+    let Ok(content) = fs::read_to_string(package.path()) else {
+        return false;
+    };
+
+    match serde_json::from_str::<serde_json::Value>(&content) {
+        Ok(package) => ["dependencies", "devDependencies", "peerDependencies"]
+            .iter()
+            .any(|key| package[key].get(name).is_some()),
+        Err(error) => {
+            log::warn!("{}", error);
+            false
+        }
+    }
+}
+
+/// Checks whether a config file (e.g. `vitest.config.ts`) with any of the JavaScript extensions exists.
+fn has_config_file(context: &Context, name: &str) -> bool {
+    CONFIG_EXTENSIONS
+        .iter()
+        .any(|ext| context.find_file(&format!("{}.{}", name, ext)).is_some())
+}
+
+/// The configured patterns along with the `.each` ones, both Jest and Vitest support them.
+fn test_patterns<T: TestFramework + ?Sized>(test_framework: &T) -> Vec<NamedPattern> {
+    vec![
+        test_framework.test_pattern().into(),
+        EACH_TEST_PATTERN.into(),
+    ]
+}
+
+fn namespace_patterns<T: TestFramework + ?Sized>(test_framework: &T) -> Vec<NamedPattern> {
+    vec![
+        test_framework.namespace_pattern().into(),
+        EACH_NAMESPACE_PATTERN.into(),
+    ]
+}
+
+/// The `-t` option picking the nearest test by its full name, Jest and Vitest take the same
+/// pattern.
+fn name_args<T: TestFramework + ?Sized>(
+    test_framework: &T,
+    context: &Context,
+) -> Result<ArgsList, Error> {
+    let nearest = test_framework.find_nearest(context)?;
+    let case = match context.case() {
+        Some(case) => Some(resolve_case(context, &nearest, case)?),
+        None => None,
+    };
+    let name = build_name_pattern(&nearest, case.as_ref());
+
+    if name.is_empty() {
+        Ok(vec![])
+    } else {
+        Ok(vec!["-t".into(), name])
+    }
+}
+
+/// The case of a parameterized title (`it.each`, `describe.each`) to run.
+#[derive(Debug, PartialEq)]
+enum Case {
+    /// The 1-based index of the row in the table, along with the values of the row when
+    /// the table is written in place.
+    Index(usize, Option<Vec<String>>),
+    /// The value of the index placeholder, or of the first placeholder without one.
+    Value(String),
+}
+
+/// Resolves the index of the case against the `.each` table of the nearest title. The index
+/// picks a single case only through the index placeholder (`%#`/`%$`) or the values of the row,
+/// without either there is no test to run.
+fn resolve_case(context: &Context, nearest: &Nearest, case: &str) -> Result<Case, Error> {
+    let Some(index) = case.parse::<usize>().ok().filter(|index| *index > 0) else {
+        return Ok(Case::Value(case.to_string()));
+    };
+    let (title, line_nr) = match nearest.tests().last() {
+        Some(test) => (test, nearest.line_nr()),
+        None => match nearest.namespaces().last() {
+            Some(namespace) => (namespace, context.line_nr()),
+            None => return Err(no_test_at_line(context)),
+        },
+    };
+    let has_index = title.contains("%#") || title.contains("%$");
+    let rows = match line_nr {
+        Some(line_nr) => table(context, line_nr)?,
+        None => None,
+    };
+
+    match rows {
+        Some(rows) => rows
+            .into_iter()
+            .nth(index - 1)
+            .map(|values| Case::Index(index, Some(values)))
+            .ok_or_else(|| no_test_at_line(context)),
+        None if has_index => Ok(Case::Index(index, None)),
+        None => Err(no_test_at_line(context)),
+    }
+}
+
+fn no_test_at_line(context: &Context) -> Error {
+    Error::NoTestAtLine {
+        path: context.path().clone(),
+        line_nr: context.line_nr_or_default(),
+    }
+}
+
+/// The rows of the nearest `.each` table at or above the line, if it is an array literal
+/// (e.g. not a tagged template or a variable).
+fn table(context: &Context, line_nr: LineNr) -> Result<Option<Vec<Vec<String>>>, Error> {
+    let Some((_, each_line_nr)) = context
+        .lines(line_nr..=1)?
+        .find(|(line, _)| line.contains(".each"))
+    else {
+        return Ok(None);
+    };
+    let source = context
+        .lines(each_line_nr..)?
+        .map(|(line, _)| line)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let Some(table) = source
+        .split_once(".each(")
+        .and_then(|(_, table)| table.trim_start().strip_prefix('['))
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(
+        utils::items(table)
+            .into_iter()
+            .map(|row| match row.strip_prefix('[') {
+                Some(values) => utils::items(values)
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect(),
+                None => vec![row.to_string()],
+            })
+            .collect(),
+    ))
+}
+
+/// Builds a pattern matching the full name of the nearest test, i.e. the namespaces and
+/// the test name joined with spaces.
+///
+/// The placeholders of parameterized titles (`it.each`, `describe.each`) match any value, so all
+/// the cases are run unless a single one is picked, see [`build_title_pattern`].
+fn build_name_pattern(nearest: &Nearest, case: Option<&Case>) -> String {
+    let titles = [nearest.namespaces(), nearest.tests()].concat();
+    let last = titles.len().saturating_sub(1);
+    let name = titles
+        .iter()
+        .enumerate()
+        .map(|(index, title)| build_title_pattern(title, if index == last { case } else { None }))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!(
+        "{}{}{}",
+        if !nearest.namespaces().is_empty() {
+            "^"
+        } else {
+            ""
+        },
+        name,
+        if !nearest.tests().is_empty() { "$" } else { "" }
+    )
+}
+
+/// Escapes the title, turning the `printf`-like (e.g. `%s`) and the `$variable` placeholders
+/// into wildcards.
+///
+/// An index fills the index placeholders (`%#` is 0-based, `%$` is 1-based) and the rest with
+/// the values of its row. A value replaces the index placeholder when there is one, otherwise
+/// the first placeholder.
+fn build_title_pattern(title: &str, case: Option<&Case>) -> String {
+    let placeholder = Regex::new(r"%[psdifjoOc#$%]|\$[A-Za-z_][\w.]*").unwrap();
+    let has_index = title.contains("%#") || title.contains("%$");
+    let mut value = match case {
+        Some(Case::Value(value)) => Some(value.as_str()),
+        _ => None,
+    };
+    let mut values = match case {
+        Some(Case::Index(_, Some(values))) => values.as_slice(),
+        _ => &[],
+    }
+    .iter();
+    let mut pattern = String::new();
+    let mut last = 0;
+
+    for found in placeholder.find_iter(title) {
+        pattern.push_str(&regex::escape(&title[last..found.start()]));
+        last = found.end();
+
+        match (found.as_str(), case) {
+            ("%%", _) => pattern.push('%'),
+            ("%#", Some(Case::Index(index, _))) => pattern.push_str(&(index - 1).to_string()),
+            ("%$", Some(Case::Index(index, _))) => pattern.push_str(&index.to_string()),
+            // The variables pick the properties of an object row, they aren't read
+            (placeholder, Some(Case::Index(..))) if !placeholder.starts_with('$') => {
+                match values.next() {
+                    Some(value) => pattern.push_str(&format_value(placeholder, value)),
+                    None => pattern.push_str(".*"),
+                }
+            }
+            (placeholder, _) if !has_index || placeholder == "%#" || placeholder == "%$" => {
+                match value.take() {
+                    Some(value) => pattern.push_str(&regex::escape(value)),
+                    None => pattern.push_str(".*"),
+                }
+            }
+            _ => pattern.push_str(".*"),
+        }
+    }
+
+    pattern.push_str(&regex::escape(&title[last..]));
+    pattern
+}
+
+/// The pattern of a value of the table formatted by the placeholder, any value for the ones
+/// that can't be formatted from the source (e.g. objects).
+fn format_value(placeholder: &str, value: &str) -> String {
+    if value.parse::<f64>().is_ok() || ["true", "false", "null", "undefined"].contains(&value) {
+        return regex::escape(value);
+    }
+
+    match (utils::unquote(value), placeholder) {
+        (Some(text), "%s") => regex::escape(text),
+        // Pretty-printed strings are quoted
+        (Some(text), "%p" | "%j" | "%o" | "%O") => regex::escape(&format!("\"{}\"", text)),
+        _ => ".*".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_title_pattern() {
+        let value = |value: &str| Some(Case::Value(value.into()));
+        let row = |index, values: &[&str]| {
+            Some(Case::Index(
+                index,
+                Some(values.iter().map(|value| value.to_string()).collect()),
+            ))
+        };
+
+        assert_eq!(build_title_pattern("adds 1 + 2", None), r"adds 1 \+ 2");
+        assert_eq!(build_title_pattern("add(%i, %i)", None), r"add\(.*, .*\)");
+        assert_eq!(
+            build_title_pattern("add(%i, %i)", value("1").as_ref()),
+            r"add\(1, .*\)"
+        );
+        assert_eq!(
+            build_title_pattern("%# add(%i)", value("1").as_ref()),
+            r"1 add\(.*\)"
+        );
+        assert_eq!(
+            build_title_pattern("add(%i, %i)", row(2, &["1", "2"]).as_ref()),
+            r"add\(1, 2\)"
+        );
+        assert_eq!(
+            build_title_pattern("%# add(%i)", Some(&Case::Index(2, None))),
+            r"1 add\(.*\)"
+        );
+        assert_eq!(
+            build_title_pattern("%$: %s is %p", row(1, &["'a'", "'b'"]).as_ref()),
+            r#"1: a is "b""#
+        );
+        assert_eq!(
+            build_title_pattern("$a adds", row(1, &["{ a: 1 }"]).as_ref()),
+            ".* adds"
+        );
+        assert_eq!(build_title_pattern("returns $expected", None), "returns .*");
+        assert_eq!(build_title_pattern("100%% of $a.b", None), "100% of .*");
+    }
+}
@@ -1,5 +1,7 @@
 use super::JavaScript;
 use crate::{
+    alternate::Projection,
+    named_pattern::NamedPattern,
     test_framework::{TestFramework, TestFrameworkMeta},
     utils,
 };
@@ -10,54 +12,61 @@ pub struct Jest {
     language: JavaScript,
     #[default = r"(__tests__[/\\].*|(spec|test))\.(js|jsx|coffee|ts|tsx)$"]
     pattern: String,
-    executable: Vec<&'static str>,
-    #[default(_code = r#"vec!["--runTestsByPath"]"#)]
-    args: Vec<&'static str>,
-    #[default = r#"^\s*(?:it|test)\s*[\( ]\s*["'\`](.*?)["'\`]"#]
+    executable: Vec<String>,
+    #[default(_code = r#"vec!["--runTestsByPath".into()]"#)]
+    args: Vec<String>,
+    #[default(_code = "super::TEST_PATTERN.into()")]
     test_pattern: String,
-    #[default = r#"^\s*(?:describe|suite|context)\s*[( ]\s*["'\`](.*?)["'\`]"#]
+    #[default(_code = "super::NAMESPACE_PATTERN.into()")]
     namespace_pattern: String,
+    #[default(_code = r#"super::projections()"#)]
+    projections: Vec<Projection>,
 }
 
-// TODO: after adding configuration handle the case when the executable contains `yarn`
-// see https://github.com/timfjord/AnyTest/blob/main/plugin/test_frameworks/javascript/jest.py#L34-L40
 impl TestFramework for Jest {
+    fn root_markers(&self) -> &[&str] {
+        &super::ROOT_MARKERS
+    }
+
+    fn test_patterns(&self) -> Vec<NamedPattern> {
+        super::test_patterns(self)
+    }
+
+    fn namespace_patterns(&self) -> Vec<NamedPattern> {
+        super::namespace_patterns(self)
+    }
+
     fn build_executable(&self, context: &crate::Context) -> crate::ArgsList {
-        if let Some(bin) = context.find_file("node_modules/.bin/jest") {
-            vec![bin.rel_str().to_string()]
-        } else {
-            vec!["jest".to_string()]
-        }
+        super::build_executable(context, "jest")
+    }
+
+    fn supports_cases(&self) -> bool {
+        true
     }
 
     fn build_file_position_args(
         &self,
         context: &crate::Context,
-    ) -> Result<crate::ArgsList, Box<dyn std::error::Error>> {
-        Ok(vec![utils::EOO.into(), context.rel_str().to_string()])
+    ) -> Result<crate::ArgsList, crate::Error> {
+        let is_yarn = self
+            .executable(context)
+            .iter()
+            .any(|arg| arg.contains("yarn"));
+
+        // `yarn` forwards options without the end of options separator and warns about it
+        if is_yarn {
+            Ok(vec![context.rel_str().to_string()])
+        } else {
+            Ok(vec![utils::EOO.into(), context.rel_str().to_string()])
+        }
     }
 
     fn build_line_position_args(
         &self,
         context: &crate::Context,
-    ) -> Result<crate::ArgsList, Box<dyn std::error::Error>> {
+    ) -> Result<crate::ArgsList, crate::Error> {
         let args = self.build_file_position_args(context)?;
-        let nearest = self.find_nearest(context)?;
-        let name = format!(
-            "{}{}{}",
-            if !nearest.namespaces().is_empty() {
-                "^"
-            } else {
-                ""
-            },
-            [nearest.namespaces(), nearest.tests()].concat().join(" "),
-            if !nearest.tests().is_empty() { "$" } else { "" }
-        );
-
-        if name.is_empty() {
-            Ok(args)
-        } else {
-            Ok(utils::concat(["-t", &utils::quote(&name)], args))
-        }
+
+        Ok(utils::concat(super::name_args(self, context)?, args))
     }
 }
@@ -0,0 +1,69 @@
+use super::JavaScript;
+use crate::{
+    alternate::Projection,
+    test_framework::{TestFramework, TestFrameworkMeta},
+    utils, ArgsList, Context, Error,
+};
+use smart_default::SmartDefault;
+
+const CONFIG_FILES: [&str; 6] = [
+    ".mocharc.js",
+    ".mocharc.cjs",
+    ".mocharc.yaml",
+    ".mocharc.yml",
+    ".mocharc.jsonc",
+    ".mocharc.json",
+];
+
+#[derive(TestFrameworkMeta, SmartDefault)]
+pub struct Mocha {
+    language: JavaScript,
+    #[default = r"^(?:.*[/\\])?test[/\\].*\.[cm]?[jt]s$"]
+    pattern: String,
+    executable: Vec<String>,
+    args: Vec<String>,
+    #[default(_code = "super::TEST_PATTERN.into()")]
+    test_pattern: String,
+    #[default(_code = "super::NAMESPACE_PATTERN.into()")]
+    namespace_pattern: String,
+    #[default(_code = r#"super::mocha_projections()"#)]
+    projections: Vec<Projection>,
+}
+
+impl TestFramework for Mocha {
+    fn root_markers(&self) -> &[&str] {
+        &super::ROOT_MARKERS
+    }
+
+    fn is_suitable_for(&self, context: &Context) -> bool {
+        self.matches_pattern(context)
+            && (CONFIG_FILES
+                .iter()
+                .any(|file| context.find_file(file).is_some())
+                || super::has_dependency(context, "mocha"))
+    }
+
+    fn build_executable(&self, context: &Context) -> ArgsList {
+        super::build_executable(context, "mocha")
+    }
+
+    // Mocha matches `--grep` against the full title, i.e. the titles of all the parents
+    // and the test title joined with spaces
+    fn build_line_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
+        let args = self.build_file_position_args(context)?;
+        let nearest = self.find_nearest(context)?;
+        let titles = [nearest.namespaces(), nearest.tests()].concat();
+
+        if titles.is_empty() {
+            return Ok(args);
+        }
+
+        let grep = format!(
+            "^{}{}",
+            regex::escape(&titles.join(" ")),
+            if nearest.has_tests() { "$" } else { " " }
+        );
+
+        Ok(utils::concat(args, ["--grep", &grep]))
+    }
+}
@@ -0,0 +1,59 @@
+use super::JavaScript;
+use crate::{
+    alternate::Projection,
+    named_pattern::NamedPattern,
+    test_framework::{TestFramework, TestFrameworkMeta},
+    utils, ArgsList, Context, Error,
+};
+use smart_default::SmartDefault;
+
+#[derive(TestFrameworkMeta, SmartDefault)]
+pub struct Vitest {
+    language: JavaScript,
+    #[default = r"(spec|test)\.[cm]?[jt]sx?$"]
+    pattern: String,
+    executable: Vec<String>,
+    #[default(_code = r#"vec!["run".into()]"#)]
+    args: Vec<String>,
+    #[default(_code = "super::TEST_PATTERN.into()")]
+    test_pattern: String,
+    #[default(_code = "super::NAMESPACE_PATTERN.into()")]
+    namespace_pattern: String,
+    #[default(_code = r#"super::projections()"#)]
+    projections: Vec<Projection>,
+}
+
+impl TestFramework for Vitest {
+    fn root_markers(&self) -> &[&str] {
+        &super::ROOT_MARKERS
+    }
+
+    fn test_patterns(&self) -> Vec<NamedPattern> {
+        super::test_patterns(self)
+    }
+
+    fn namespace_patterns(&self) -> Vec<NamedPattern> {
+        super::namespace_patterns(self)
+    }
+
+    fn is_suitable_for(&self, context: &Context) -> bool {
+        self.matches_pattern(context)
+            && (super::has_config_file(context, "vitest.config")
+                || (super::has_config_file(context, "vite.config")
+                    && super::has_dependency(context, "vitest")))
+    }
+
+    fn build_executable(&self, context: &Context) -> ArgsList {
+        super::build_executable(context, "vitest")
+    }
+
+    fn supports_cases(&self) -> bool {
+        true
+    }
+
+    fn build_line_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
+        let args = self.build_file_position_args(context)?;
+
+        Ok(utils::concat(args, super::name_args(self, context)?))
+    }
+}
@@ -1,9 +1,112 @@
-use crate::language::Language;
+use crate::{
+    alternate::Projection, context::Nearest, language::Language, utils, ArgsList, Context,
+};
+use std::fs;
 
 // Test frameworks
+pub use djangotest::DjangoTest;
+pub use nose2::Nose2;
 pub use pytest::Pytest;
+pub use pyunit::PyUnit;
 
+mod djangotest;
+mod nose2;
+mod parametrize;
 mod pytest;
+mod pyunit;
 
 #[derive(Language, Default)]
 struct Python {}
+
+const PATTERN: &str = r"(test_[^/\\]+|[^/\\]+_test)\.py$";
+const TEST_PATTERN: &str = r"\s*(?:async )?def (test_\w+)";
+const NAMESPACE_PATTERN: &str = r"\s*class (\w+)";
+
+/// A Python project, the module paths are relative to its config files.
+const ROOT_MARKERS: [&str; 5] = [
+    "pyproject.toml",
+    "setup.py",
+    "setup.cfg",
+    "pytest.ini",
+    "tox.ini",
+];
+
+/// The test files either in `tests` (mirroring the package or flat) or next to the module.
+fn projections() -> Vec<Projection> {
+    vec![
+        Projection::new("src/**/*.py", "tests/**/test_*.py"),
+        Projection::new("**/*.py", "tests/**/test_*.py"),
+        Projection::new("**/*.py", "tests/test_*.py"),
+        Projection::new("**/*.py", "**/test_*.py"),
+        Projection::new("**/*.py", "**/tests/test_*.py"),
+    ]
+}
+
+/// Runs the executable through the project's package manager (Pipenv, Poetry or PDM).
+fn build_executable(context: &Context, executable: ArgsList) -> ArgsList {
+    if context.find_file("Pipfile").is_some() {
+        utils::concat(["pipenv", "run"], executable)
+    } else if context.find_file("poetry.lock").is_some() {
+        utils::concat(["poetry", "run"], executable)
+    } else if context.find_file("pdm.lock").is_some() {
+        utils::concat(["pdm", "run"], executable)
+    } else {
+        executable
+    }
+}
+
+fn file_contains(context: &Context, file: &str, needle: &str) -> bool {
+    context
+        .find_file(file)
+        .and_then(|file| fs::read_to_string(file.path()).ok())
+        .is_some_and(|content| content.contains(needle))
+}
+
+fn has_pytest_config(context: &Context) -> bool {
+    context.find_file("pytest.ini").is_some()
+        || context.find_file("conftest.py").is_some()
+        || file_contains(context, "pyproject.toml", "[tool.pytest")
+        || file_contains(context, "setup.cfg", "[tool:pytest]")
+        || file_contains(context, "tox.ini", "[pytest]")
+}
+
+/// Converts the relative path to a dotted module path, e.g. `tests/test_foo.py` -> `tests.test_foo`.
+fn module_path(context: &Context) -> String {
+    context
+        .rel()
+        .with_extension("")
+        .components()
+        .map(|c| c.as_os_str().to_str().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Builds a `module.Class.test_method` name understood by the unittest loader.
+fn build_dotted_name(context: &Context, nearest: &Nearest) -> String {
+    [
+        &[module_path(context)],
+        nearest.namespaces(),
+        nearest.tests(),
+    ]
+    .concat()
+    .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_path() {
+        let context = Context::new(
+            Some("tests/fixtures/nose2"),
+            "tests/test_class.py",
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(module_path(&context), "tests.test_class");
+    }
+}
@@ -0,0 +1,57 @@
+use super::Python;
+use crate::{
+    alternate::Projection,
+    test_framework::{self, TestFramework, TestFrameworkMeta},
+    ArgsList, Context, Error,
+};
+use smart_default::SmartDefault;
+
+#[derive(TestFrameworkMeta, SmartDefault)]
+pub struct DjangoTest {
+    language: Python,
+    // Unlike the shared pattern, it matches the `tests.py` modules Django generates
+    #[default = r"(^|[/\\])(test[^/\\]*|[^/\\]+_test)\.py$"]
+    pattern: String,
+    executable: Vec<String>,
+    args: Vec<String>,
+    #[default(_code = "super::TEST_PATTERN.into()")]
+    test_pattern: String,
+    #[default(_code = "super::NAMESPACE_PATTERN.into()")]
+    namespace_pattern: String,
+    #[default(_code = r#"super::projections()"#)]
+    projections: Vec<Projection>,
+}
+
+impl TestFramework for DjangoTest {
+    fn root_markers(&self) -> &[&str] {
+        &["manage.py"]
+    }
+
+    fn is_suitable_for(&self, context: &Context) -> bool {
+        // pytest-django projects have `manage.py` too but run their tests with pytest
+        self.matches_pattern(context)
+            && context.find_file("manage.py").is_some()
+            && !super::has_pytest_config(context)
+    }
+
+    fn build_executable(&self, context: &Context) -> ArgsList {
+        super::build_executable(
+            context,
+            vec!["python".into(), "manage.py".into(), "test".into()],
+        )
+    }
+
+    fn build_file_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
+        Ok(vec![super::module_path(context)])
+    }
+
+    fn build_line_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
+        let nearest = self.find_nearest(context)?;
+
+        Ok(vec![super::build_dotted_name(context, &nearest)])
+    }
+
+    fn merge_position_args(&self, contexts: &[&Context]) -> Result<Vec<ArgsList>, Error> {
+        test_framework::concat_position_args(self, contexts)
+    }
+}
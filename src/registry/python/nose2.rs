@@ -0,0 +1,62 @@
+use super::Python;
+use crate::{
+    alternate::Projection,
+    test_framework::{self, TestFramework, TestFrameworkMeta},
+    utils, ArgsList, Context, Error,
+};
+use smart_default::SmartDefault;
+
+#[derive(TestFrameworkMeta, SmartDefault)]
+pub struct Nose2 {
+    language: Python,
+    #[default(_code = "super::PATTERN.into()")]
+    pattern: String,
+    executable: Vec<String>,
+    args: Vec<String>,
+    #[default(_code = "super::TEST_PATTERN.into()")]
+    test_pattern: String,
+    #[default(_code = "super::NAMESPACE_PATTERN.into()")]
+    namespace_pattern: String,
+    #[default(_code = r#"super::projections()"#)]
+    projections: Vec<Projection>,
+}
+
+fn has_config(context: &Context) -> bool {
+    context.find_file("nose2.cfg").is_some()
+        || context.find_file("unittest.cfg").is_some()
+        || super::file_contains(context, "pyproject.toml", "[tool.nose2")
+}
+
+impl TestFramework for Nose2 {
+    fn root_markers(&self) -> &[&str] {
+        &super::ROOT_MARKERS
+    }
+
+    fn is_suitable_for(&self, context: &Context) -> bool {
+        self.matches_pattern(context) && !super::has_pytest_config(context) && has_config(context)
+    }
+
+    fn build_executable(&self, context: &Context) -> ArgsList {
+        let executable: ArgsList = if utils::is_executable("nose2") {
+            vec!["nose2".into()]
+        } else {
+            vec!["python".into(), "-m".into(), "nose2".into()]
+        };
+
+        super::build_executable(context, executable)
+    }
+
+    fn build_file_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
+        Ok(vec![super::module_path(context)])
+    }
+
+    fn build_line_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
+        let nearest = self.find_nearest(context)?;
+
+        Ok(vec![super::build_dotted_name(context, &nearest)])
+    }
+
+    fn merge_position_args(&self, contexts: &[&Context]) -> Result<Vec<ArgsList>, Error> {
+        test_framework::concat_position_args(self, contexts)
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{
+    context::Nearest,
+    utils::{items, unquote},
+    Context, Error, LineNr,
+};
+
+/// The ID of the case to run, picked either by its 1-based index or by the ID itself.
+///
+/// The index is resolved against the IDs of `parametrize`, either the explicit `ids` or the ones
+/// pytest generates from the values. It can't be resolved for the stacked or the dynamic
+/// parametrizations, so there is no test to run then.
+pub(super) fn case_id(context: &Context, nearest: &Nearest, case: &str) -> Result<String, Error> {
+    let Some(index) = case.parse::<usize>().ok().filter(|index| *index > 0) else {
+        return Ok(case.to_string());
+    };
+    let decorators = match nearest.line_nr() {
+        Some(line_nr) => decorators(context, line_nr)?,
+        None => String::new(),
+    };
+
+    case_ids(&decorators)
+        .and_then(|ids| ids.into_iter().nth(index - 1))
+        .ok_or_else(|| Error::NoTestAtLine {
+            path: context.path().clone(),
+            line_nr: context.line_nr_or_default(),
+        })
+}
+
+/// The decorators above the function, up to the previous statement.
+fn decorators(context: &Context, def_line_nr: LineNr) -> Result<String, Error> {
+    if def_line_nr <= 1 {
+        return Ok(String::new());
+    }
+
+    let mut lines: Vec<_> = context
+        .lines(def_line_nr - 1..=1)?
+        .map(|(line, _)| line)
+        .take_while(|line| {
+            let line = line.trim();
+            !line.is_empty() && !line.ends_with(':')
+        })
+        .collect();
+    lines.reverse();
+
+    Ok(lines.join("\n"))
+}
+
+/// The IDs of the cases of a single `parametrize`, in order.
+fn case_ids(decorators: &str) -> Option<Vec<String>> {
+    let mut calls = decorators.match_indices("parametrize(");
+    let (start, call) = calls.next()?;
+
+    // The IDs of the stacked parametrizations are combined in the order they are applied
+    if calls.next().is_some() {
+        return None;
+    }
+
+    let args = items(&decorators[start + call.len()..]);
+    let keyword = |name: &str| {
+        args.iter().find_map(|arg| {
+            arg.strip_prefix(name)
+                .and_then(|arg| arg.trim_start().strip_prefix('='))
+                .map(str::trim_start)
+        })
+    };
+
+    if let Some(ids) = keyword("ids") {
+        return items(ids.strip_prefix('[')?)
+            .into_iter()
+            .map(|id| unquote(id).map(str::to_string))
+            .collect();
+    }
+
+    let names: Vec<&str> = match list(args.first()?) {
+        Some(names) => names.into_iter().map(unquote).collect::<Option<_>>()?,
+        None => unquote(args.first()?)?.split(',').map(str::trim).collect(),
+    };
+    let values = items(args.get(1)?.strip_prefix('[')?);
+
+    Some(
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| generated_id(&names, value, index))
+            .collect(),
+    )
+}
+
+/// The ID pytest generates for the values of a case, e.g. `1-2-3` for `(1, 2, 3)`.
+fn generated_id(names: &[&str], value: &str, index: usize) -> String {
+    let values = if let Some(param) = value.strip_prefix("pytest.param(") {
+        let args = items(param);
+
+        if let Some(id) = args.iter().find_map(|arg| {
+            arg.strip_prefix("id")
+                .and_then(|arg| arg.trim_start().strip_prefix('='))
+                .and_then(|id| unquote(id.trim_start()))
+        }) {
+            return id.to_string();
+        }
+
+        args.into_iter().filter(|arg| !arg.contains('=')).collect()
+    } else if names.len() > 1 {
+        list(value).unwrap_or_default()
+    } else {
+        vec![value]
+    };
+
+    values
+        .into_iter()
+        .zip(names)
+        .map(|(value, name)| {
+            if let Some(value) = unquote(value) {
+                value.to_string()
+            } else if value.parse::<f64>().is_ok() || ["True", "False", "None"].contains(&value) {
+                value.to_string()
+            } else {
+                format!("{}{}", name, index)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// The items of a tuple or a list literal.
+fn list(text: &str) -> Option<Vec<&str>> {
+    text.strip_prefix(['(', '[']).map(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case_ids() {
+        assert_eq!(case_ids("@pytest.mark.slow"), None);
+        assert_eq!(
+            case_ids(r#"@pytest.mark.parametrize("a,b", [(1, 1), (1, 2)], ids=["ones", "twos"])"#),
+            Some(vec!["ones".into(), "twos".into()])
+        );
+        assert_eq!(
+            case_ids("@pytest.mark.parametrize(\n    \"a,b\",\n    [(1, \"x\"), (2.5, None)],\n)"),
+            Some(vec!["1-x".into(), "2.5-None".into()])
+        );
+        assert_eq!(
+            case_ids(
+                r#"@pytest.mark.parametrize(("a", "b"), [(1, [1]), pytest.param(2, 2, id="two")])"#
+            ),
+            Some(vec!["1-b0".into(), "two".into()])
+        );
+        assert_eq!(
+            case_ids(
+                "@pytest.mark.parametrize(\"a\", [1, 2])\n@pytest.mark.parametrize(\"b\", [3])"
+            ),
+            None
+        );
+    }
+}
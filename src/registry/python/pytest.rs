@@ -1,9 +1,8 @@
-use std::error::Error;
-
-use super::Python;
+use super::{parametrize, Python};
 use crate::{
-    test_framework::{TestFramework, TestFrameworkMeta},
-    utils, ArgsList, Context,
+    alternate::Projection,
+    test_framework::{self, TestFramework, TestFrameworkMeta},
+    utils, ArgsList, Context, Error,
 };
 use smart_default::SmartDefault;
 
@@ -12,17 +11,23 @@ const SEPARATOR: &str = "::";
 #[derive(TestFrameworkMeta, SmartDefault)]
 pub struct Pytest {
     language: Python,
-    #[default = r"(test_[^/\\]+|[^/\\]+_test)\.py$"]
+    #[default(_code = "super::PATTERN.into()")]
     pattern: String,
-    executable: Vec<&'static str>,
-    args: Vec<&'static str>,
-    #[default = r"\s*(?:async )?def (test_\w+)"]
+    executable: Vec<String>,
+    args: Vec<String>,
+    #[default(_code = "super::TEST_PATTERN.into()")]
     test_pattern: String,
-    #[default = r"\s*class (\w+)"]
+    #[default(_code = "super::NAMESPACE_PATTERN.into()")]
     namespace_pattern: String,
+    #[default(_code = r#"super::projections()"#)]
+    projections: Vec<Projection>,
 }
 
 impl TestFramework for Pytest {
+    fn root_markers(&self) -> &[&str] {
+        &super::ROOT_MARKERS
+    }
+
     fn build_executable(&self, context: &Context) -> ArgsList {
         let executable: ArgsList = if utils::is_executable("pytest") {
             vec!["pytest".into()]
@@ -32,24 +37,33 @@ impl TestFramework for Pytest {
             vec!["python".into(), "-m".into(), "pytest".into()]
         };
 
-        if context.find_file("Pipfile").is_some() {
-            utils::concat(["pipenv", "run"], executable)
-        } else if context.find_file("poetry.lock").is_some() {
-            utils::concat(["poetry", "run"], executable)
-        } else if context.find_file("pdm.lock").is_some() {
-            utils::concat(["pdm", "run"], executable)
-        } else {
-            executable
-        }
+        super::build_executable(context, executable)
     }
 
-    fn build_line_position_args(&self, context: &Context) -> Result<ArgsList, Box<dyn Error>> {
+    fn supports_cases(&self) -> bool {
+        true
+    }
+
+    fn build_line_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
         let file_args = self.build_file_position_args(context)?;
         let nearest = self.find_nearest(context)?;
 
         let arg = [&file_args, nearest.namespaces(), nearest.tests()]
             .concat()
             .join(SEPARATOR);
-        Ok(vec![arg])
+
+        // The node ID of a parametrized test runs all of its cases, the case ID picks one
+        match context.case() {
+            Some(case) if nearest.has_tests() => Ok(vec![format!(
+                "{}[{}]",
+                arg,
+                parametrize::case_id(context, &nearest, case)?
+            )]),
+            _ => Ok(vec![arg]),
+        }
+    }
+
+    fn merge_position_args(&self, contexts: &[&Context]) -> Result<Vec<ArgsList>, Error> {
+        test_framework::concat_position_args(self, contexts)
     }
 }
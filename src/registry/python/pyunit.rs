@@ -0,0 +1,73 @@
+use super::Python;
+use crate::{
+    alternate::Projection,
+    test_framework::{self, TestFramework, TestFrameworkMeta},
+    ArgsList, Context, Error,
+};
+use regex::Regex;
+use smart_default::SmartDefault;
+use std::fs;
+
+#[derive(TestFrameworkMeta, SmartDefault)]
+pub struct PyUnit {
+    language: Python,
+    #[default(_code = "super::PATTERN.into()")]
+    pattern: String,
+    executable: Vec<String>,
+    args: Vec<String>,
+    #[default(_code = "super::TEST_PATTERN.into()")]
+    test_pattern: String,
+    #[default(_code = "super::NAMESPACE_PATTERN.into()")]
+    namespace_pattern: String,
+    #[default(_code = r#"super::projections()"#)]
+    projections: Vec<Projection>,
+}
+
+/// Whether the file defines a `unittest` test case. Importing `unittest` alone isn't enough as
+/// the pytest tests use `unittest.mock` too.
+fn defines_test_case(context: &Context) -> bool {
+    let Ok(content) = fs::read_to_string(context.path()) else {
+        return false;
+    };
+    let patterns = [
+        r"(?m)^\s*(?:import\s+(?:[\w.]+\s*,\s*)*unittest\s*(?:$|,|#|as\b)|from\s+unittest\s+import\b)",
+        r"(?m)^\s*class\s+\w+\s*\((?:unittest\.)?\w*TestCase\b",
+    ];
+
+    patterns
+        .into_iter()
+        .all(|pattern| Regex::new(pattern).is_ok_and(|regex| regex.is_match(&content)))
+}
+
+impl TestFramework for PyUnit {
+    fn root_markers(&self) -> &[&str] {
+        &super::ROOT_MARKERS
+    }
+
+    fn is_suitable_for(&self, context: &Context) -> bool {
+        self.matches_pattern(context)
+            && !super::has_pytest_config(context)
+            && defines_test_case(context)
+    }
+
+    fn build_executable(&self, context: &Context) -> ArgsList {
+        super::build_executable(
+            context,
+            vec!["python".into(), "-m".into(), "unittest".into()],
+        )
+    }
+
+    fn build_file_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
+        Ok(vec![super::module_path(context)])
+    }
+
+    fn build_line_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
+        let nearest = self.find_nearest(context)?;
+
+        Ok(vec![super::build_dotted_name(context, &nearest)])
+    }
+
+    fn merge_position_args(&self, contexts: &[&Context]) -> Result<Vec<ArgsList>, Error> {
+        test_framework::concat_position_args(self, contexts)
+    }
+}
@@ -1,9 +1,38 @@
-use crate::language::Language;
+use crate::{language::Language, utils, ArgsList, Context};
 
 // Test frameworks
+pub use minitest::Minitest;
 pub use rspec::RSpec;
 
+mod minitest;
 mod rspec;
 
 #[derive(Language, Default)]
 struct Ruby {}
+
+/// A Bundler project, the bin stubs and the preloaders live next to the `Gemfile`.
+const ROOT_MARKERS: [&str; 1] = ["Gemfile"];
+
+/// Runs the executable through a preloader (Zeus or Spring), its bin stub (e.g. `bin/rspec`)
+/// or Bundler, depending on what the project uses.
+fn build_executable(context: &Context, executable: ArgsList) -> ArgsList {
+    let program = executable.first().cloned().unwrap_or_default();
+
+    if context.find_file(".zeus.sock").is_some() {
+        utils::concat(["zeus"], executable)
+    } else if let Some(spring_bin) = context.find_file("bin/spring") {
+        utils::concat([spring_bin.rel_str()], executable)
+    } else if let Some(bin) = context.find_file(&format!("bin/{}", program)) {
+        utils::concat([bin.rel_str()], &executable[1..])
+    } else {
+        bundle_exec(context, executable)
+    }
+}
+
+fn bundle_exec(context: &Context, executable: ArgsList) -> ArgsList {
+    if context.find_file("Gemfile").is_some() {
+        utils::concat(["bundle", "exec"], executable)
+    } else {
+        executable
+    }
+}
@@ -0,0 +1,105 @@
+use super::Ruby;
+use crate::{
+    alternate::Projection,
+    named_pattern::NamedPattern,
+    test_framework::{self, TestFramework, TestFrameworkMeta},
+    utils, ArgsList, Context, Error, Scope,
+};
+use regex::Regex;
+use smart_default::SmartDefault;
+
+const STRING_TEST: &str = "string";
+
+#[derive(TestFrameworkMeta, SmartDefault)]
+pub struct Minitest {
+    language: Ruby,
+    #[default = r"_test\.rb$"]
+    pattern: String,
+    executable: Vec<String>,
+    args: Vec<String>,
+    #[default = r"^\s*def\s+(test_\w+)"]
+    test_pattern: String,
+    #[default = r"^\s*(?:class|module)\s+(\S+)"]
+    namespace_pattern: String,
+    #[default(_code = r#"vec![
+        Projection::new("app/**/*.rb", "test/**/*_test.rb"),
+        Projection::new("lib/**/*.rb", "test/**/*_test.rb"),
+        Projection::new("lib/**/*.rb", "test/lib/**/*_test.rb"),
+    ]"#)]
+    projections: Vec<Projection>,
+
+    // ActiveSupport's `test "..." do` blocks
+    #[default = r#"^\s*test\s*\(?\s*["'](.*?)["']"#]
+    string_test_pattern: String,
+}
+
+fn is_rails(context: &Context) -> bool {
+    context.find_file("bin/rails").is_some()
+}
+
+impl TestFramework for Minitest {
+    fn root_markers(&self) -> &[&str] {
+        &super::ROOT_MARKERS
+    }
+
+    fn test_patterns(&self) -> Vec<NamedPattern> {
+        vec![
+            self.test_pattern().into(),
+            NamedPattern::new(&self.string_test_pattern, Some(STRING_TEST)),
+        ]
+    }
+
+    fn build_executable(&self, context: &Context) -> ArgsList {
+        if is_rails(context) {
+            super::build_executable(context, vec!["rails".into(), "test".into()])
+        } else if matches!(context.scope(), Scope::Suite) {
+            super::bundle_exec(context, vec!["rake".into(), "test".into()])
+        } else {
+            super::bundle_exec(context, vec!["ruby".into(), "-Itest".into()])
+        }
+    }
+
+    fn build_line_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
+        // The Rails test runner understands line numbers
+        if is_rails(context) {
+            return Ok(vec![context.rel_full()]);
+        }
+
+        let args = self.build_file_position_args(context)?;
+        let nearest = self.find_nearest(context)?;
+        let class_name = regex::escape(&nearest.namespaces().join("::"));
+        let pattern = match nearest.tests().first() {
+            Some(test) => {
+                // `test "adds two numbers"` defines the `test_adds_two_numbers` method
+                let method_name = if nearest.names().iter().any(|name| name == STRING_TEST) {
+                    format!("test_{}", Regex::new(r"\s+")?.replace_all(test, "_"))
+                } else {
+                    test.to_string()
+                };
+
+                if class_name.is_empty() {
+                    format!("^{}$", regex::escape(&method_name))
+                } else {
+                    format!("^{}#{}$", class_name, regex::escape(&method_name))
+                }
+            }
+            None if !class_name.is_empty() => format!("^{}#", class_name),
+            None => return Ok(args),
+        };
+
+        Ok(utils::concat(args, ["-n".into(), format!("/{}/", pattern)]))
+    }
+
+    fn merge_position_args(&self, contexts: &[&Context]) -> Result<Vec<ArgsList>, Error> {
+        // Only the Rails test runner accepts several files, `ruby` runs a single one
+        match contexts.first() {
+            Some(context) if is_rails(context) => {
+                test_framework::concat_position_args(self, contexts)
+            }
+            _ => contexts
+                .iter()
+                .map(|context| self.position_args(context))
+                .collect(),
+        }
+    }
+}
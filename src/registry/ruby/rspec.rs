@@ -1,7 +1,8 @@
 use super::Ruby;
 use crate::{
-    test_framework::{TestFramework, TestFrameworkMeta},
-    utils, ArgsList, Context,
+    alternate::Projection,
+    test_framework::{self, TestFramework, TestFrameworkMeta},
+    utils, ArgsList, Context, Error,
 };
 use smart_default::SmartDefault;
 
@@ -10,26 +11,48 @@ pub struct RSpec {
     language: Ruby,
     #[default = r"(_spec\.rb|spec[/\\].*\.feature)$"]
     pattern: String,
-    args: Vec<&'static str>,
-    executable: Vec<&'static str>,
+    args: Vec<String>,
+    executable: Vec<String>,
+    #[default = r#"^\s*(?:it|specify|example|scenario|it_behaves_like|it_should_behave_like|include_examples)\s*[( ]\s*["'](.*?)["']"#]
     test_pattern: String,
+    #[default = r#"^\s*(?:RSpec\.)?(?:describe|context|feature)\s*[( ]\s*["'](.*?)["']"#]
     namespace_pattern: String,
+    #[default(_code = r#"vec![
+        Projection::new("app/**/*.rb", "spec/**/*_spec.rb"),
+        Projection::new("lib/**/*.rb", "spec/**/*_spec.rb"),
+        Projection::new("lib/**/*.rb", "spec/lib/**/*_spec.rb"),
+    ]"#)]
+    projections: Vec<Projection>,
 }
 
 impl TestFramework for RSpec {
+    fn root_markers(&self) -> &[&str] {
+        &super::ROOT_MARKERS
+    }
+
     fn build_executable(&self, context: &Context) -> ArgsList {
-        let executable: ArgsList = vec!["rspec".into()];
+        super::build_executable(context, vec!["rspec".into()])
+    }
+
+    fn supports_cases(&self) -> bool {
+        true
+    }
+
+    fn build_line_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
+        let args = vec![context.rel_full()];
 
-        if context.find_file(".zeus.sock").is_some() {
-            utils::concat(["zeus"], executable)
-        } else if let Some(spring_bin) = context.find_file("bin/spring") {
-            utils::concat([spring_bin.rel_str()], executable)
-        } else if let Some(bin) = context.find_file("bin/rspec") {
-            vec![bin.rel_str().to_string()]
-        } else if context.find_file("Gemfile").is_some() {
-            utils::concat(["bundle", "exec"], executable)
-        } else {
-            executable
+        // The examples of a shared group or a loop share the line, so pick one by its description,
+        // `-e` matches a substring of it so an index would pick the wrong examples
+        match context.case() {
+            Some(case) if case.parse::<usize>().is_ok() => Err(Error::CaseNotSupported {
+                framework: self.name().into(),
+            }),
+            Some(case) => Ok(utils::concat(args, ["-e", case])),
+            None => Ok(args),
         }
     }
+
+    fn merge_position_args(&self, contexts: &[&Context]) -> Result<Vec<ArgsList>, Error> {
+        test_framework::concat_position_args(self, contexts)
+    }
 }
@@ -1,11 +1,240 @@
-use crate::language::Language;
+use crate::{context::Nearest, language::Language, ArgsList, Context, Error};
+use manifest::Manifest;
+use std::path::{Path, PathBuf};
 
 // Test frameworks
 pub use cargotest::Cargotest;
+pub use nextest::Nextest;
 
 mod cargotest;
+mod doctest;
+mod manifest;
+mod modules;
+mod nextest;
+mod parameterized;
 
 const MANIFEST_FILE: &str = "Cargo.toml";
+const SEPARATOR: &str = "::";
+// Shared by `cargo test` and nextest, both run the same tests
+const PATTERN: &str = r".rs$";
+const TEST_PATTERN: &str = r"(#\[(?:\w+::|rs)?test)";
+const NAMESPACE_PATTERN: &str = r"^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)\s*\{";
+// The test pattern matches the attribute, this one the function after it
+const FORWARD_TEST_PATTERN: &str = r"\s*(?:async )?fn\s+(\w+)";
 
 #[derive(Language, Default)]
 struct Rust {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TargetKind {
+    Lib,
+    Bin,
+    Test,
+    Bench,
+    Example,
+}
+
+impl TargetKind {
+    fn name(&self) -> &str {
+        match self {
+            Self::Lib => "lib",
+            Self::Bin => "bin",
+            Self::Test => "test",
+            Self::Bench => "bench",
+            Self::Example => "example",
+        }
+    }
+}
+
+/// A target of a package, e.g. the library or an integration test.
+#[derive(Debug, Clone, PartialEq)]
+struct Target {
+    kind: TargetKind,
+    name: String,
+    /// The crate root relative to the package directory, e.g. `src/lib.rs`.
+    path: PathBuf,
+}
+
+impl Target {
+    /// The `cargo test` arguments selecting the target, e.g. `--test foo`.
+    fn cargo_args(&self) -> ArgsList {
+        match self.kind {
+            TargetKind::Lib => vec!["--lib".into()],
+            kind => vec![format!("--{}", kind.name()), self.name.clone()],
+        }
+    }
+
+    fn dir(&self) -> &Path {
+        self.path.parent().unwrap_or(Path::new(""))
+    }
+}
+
+/// Where the file lives in the Cargo project.
+#[derive(Debug, Default, PartialEq)]
+struct Location {
+    /// The package, only set when the file isn't in the root package.
+    package: Option<String>,
+    /// The target the file belongs to, unset when it can't be told (e.g. a module shared by
+    /// several integration tests).
+    target: Option<Target>,
+    /// The module path of the file within its target.
+    modules: Vec<String>,
+}
+
+impl Location {
+    /// The module path prefixed to the test name, e.g. `foo::bar::`.
+    fn module_prefix(&self) -> String {
+        if self.modules.is_empty() {
+            String::new()
+        } else {
+            [&self.modules[..], &["".into()]].concat().join(SEPARATOR)
+        }
+    }
+}
+
+// The test pattern matches the test attribute, so the name is the first function after it
+fn nearest_test_name(
+    context: &Context,
+    nearest: &Nearest,
+    forward_test_pattern: &str,
+) -> Result<Option<String>, Error> {
+    let Some(line_nr) = nearest.line_nr() else {
+        return Ok(None);
+    };
+    let forward_nearest = context.find_nearest(
+        &[forward_test_pattern.into()],
+        Default::default(),
+        line_nr..,
+    )?;
+
+    Ok(forward_nearest.tests().first().cloned())
+}
+
+/// The test name along with its test module, e.g. `tests::it_works`.
+fn test_path(nearest: &Nearest, test_name: String) -> String {
+    [nearest.namespaces(), &[test_name]]
+        .concat()
+        .join(SEPARATOR)
+}
+
+/// Finds the package, the target and the module path of the file, using the nearest manifest.
+fn find_location(context: &Context) -> Result<Location, Error> {
+    let (package_dir, manifest) = find_package(context)?;
+    let path = context.rel().strip_prefix(&package_dir)?;
+    let package = if package_dir.as_os_str().is_empty() {
+        None
+    } else {
+        Some(package_name(context, &package_dir, manifest.as_ref()))
+    };
+    let targets = manifest
+        .as_ref()
+        .map(|manifest| {
+            manifest.targets(
+                &context.root().join(&package_dir),
+                &package_name(context, &package_dir, Some(manifest)),
+            )
+        })
+        .unwrap_or_default();
+
+    if let Some(target) = targets.iter().find(|target| target.path == path) {
+        return Ok(Location {
+            package,
+            target: Some(target.clone()),
+            modules: vec![],
+        });
+    }
+
+    // The file is a module of the target whose crate root is in the closest directory,
+    // modules in `src` belong to the library rather than the binary
+    let candidates = targets
+        .iter()
+        .filter(|target| path.starts_with(target.dir()))
+        .collect::<Vec<_>>();
+    let depth = candidates
+        .iter()
+        .map(|target| target.dir().components().count())
+        .max();
+    let candidates = candidates
+        .into_iter()
+        .filter(|target| Some(target.dir().components().count()) == depth)
+        .collect::<Vec<_>>();
+    let target = candidates
+        .iter()
+        .find(|target| target.kind == TargetKind::Lib)
+        .or_else(|| {
+            candidates
+                .iter()
+                .find(|target| target.kind == TargetKind::Bin)
+        })
+        .or(if candidates.len() == 1 {
+            candidates.first()
+        } else {
+            None
+        })
+        .map(|target| (*target).clone());
+
+    let declared_modules = target.as_ref().and_then(|target| {
+        modules::resolve(&context.root().join(&package_dir), &target.path, path)
+    });
+    let mut modules = match candidates.first() {
+        Some(candidate) => path
+            .strip_prefix(candidate.dir())?
+            .with_extension("")
+            .components()
+            .map(|c| c.as_os_str().to_str().unwrap_or_default().to_string())
+            .collect::<Vec<String>>(),
+        // Not a part of any target, assume it is a module in `src`
+        None => path
+            .with_extension("")
+            .components()
+            .skip(1)
+            .map(|c| c.as_os_str().to_str().unwrap_or_default().to_string())
+            .collect::<Vec<String>>(),
+    };
+
+    if modules.last().is_some_and(|module| module == "mod") {
+        modules.pop();
+    }
+
+    Ok(Location {
+        package,
+        target,
+        modules: declared_modules.unwrap_or(modules),
+    })
+}
+
+/// The directory of the nearest package manifest relative to the root, along with the
+/// manifest itself (if there is one).
+fn find_package(context: &Context) -> Result<(PathBuf, Option<Manifest>), Error> {
+    for dir in context.rel().ancestors().skip(1) {
+        let manifest_path = context.root().join(dir).join(MANIFEST_FILE);
+
+        if !manifest_path.is_file() {
+            continue;
+        }
+
+        let manifest = Manifest::load(&manifest_path)?;
+
+        if !manifest.is_virtual() {
+            return Ok((dir.to_path_buf(), Some(manifest)));
+        }
+    }
+
+    Ok((PathBuf::new(), None))
+}
+
+/// The package name from the manifest, falling back to the package directory name.
+fn package_name(context: &Context, package_dir: &Path, manifest: Option<&Manifest>) -> String {
+    manifest
+        .and_then(|manifest| manifest.package_name())
+        .map(|name| name.to_string())
+        .or_else(|| {
+            context
+                .root()
+                .join(package_dir)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.to_string())
+        })
+        .unwrap_or_default()
+}
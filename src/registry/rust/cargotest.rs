@@ -1,118 +1,203 @@
-use super::Rust;
+use super::{parameterized::Cases, Rust};
 use crate::{
+    alternate::Projection,
+    context::Nearest,
     test_framework::{TestFramework, TestFrameworkMeta},
-    utils, ArgsList,
+    utils, ArgsList, Context, Error, Scope,
 };
-use regex::Regex;
 use smart_default::SmartDefault;
-use std::iter;
-use std::path;
-
-const SEPARATOR: &str = "::";
 
 #[derive(TestFrameworkMeta, SmartDefault)]
 pub struct Cargotest {
     language: Rust,
-    #[default = r".rs$"]
+    #[default(_code = "super::PATTERN.into()")]
     pattern: String,
-    #[default(_code = r#"vec!["cargo", "test"]"#)]
-    executable: Vec<&'static str>,
-    args: Vec<&'static str>,
-    #[default = r"(#\[(?:\w+::|rs)?test)"]
+    #[default(_code = r#"vec!["cargo".into(), "test".into()]"#)]
+    executable: Vec<String>,
+    args: Vec<String>,
+    #[default(_code = "super::TEST_PATTERN.into()")]
     test_pattern: String,
-    #[default = r"mod (tests?)"]
+    #[default(_code = "super::NAMESPACE_PATTERN.into()")]
     namespace_pattern: String,
+    // The tests live next to the code
+    projections: Vec<Projection>,
 
-    #[default = r"\s*(?:async )?fn\s+(\w+)"]
+    #[default(_code = "super::FORWARD_TEST_PATTERN.into()")]
     forward_test_pattern: String,
 }
 
-impl TestFramework for Cargotest {
-    // TODO: Refactor this method, since it is almost the exact adaptation of the Python implementation
-    //       (which is the adaptation of the Vimscript implementation).
-    fn build_file_position_args(
-        &self,
-        context: &crate::Context,
-    ) -> Result<ArgsList, Box<dyn std::error::Error>> {
-        let mut args = vec![];
+/// The tests of a target, `cargo test` runs a single target per invocation but accepts
+/// several filters.
+#[derive(Debug, Default, PartialEq)]
+struct Selection {
+    target_args: ArgsList,
+    /// No filters select all the tests of the target.
+    filters: Vec<String>,
+    exact: bool,
+}
 
-        let mut modules = context
-            .rel()
-            .with_extension("")
-            .components()
-            .map(|c| c.as_os_str().to_str().unwrap_or_default().to_string())
-            .collect::<Vec<String>>();
-
-        if Regex::new(r"^(main|lib|mod)$")?
-            .is_match(modules.last().ok_or("Relative path is invalid")?)
-        {
-            modules.pop();
+impl Selection {
+    fn new(target_args: ArgsList, filter: Option<String>, exact: bool) -> Self {
+        Self {
+            target_args,
+            filters: filter.into_iter().collect(),
+            exact,
         }
+    }
+
+    fn can_merge(&self, other: &Self) -> bool {
+        self.target_args == other.target_args
+            && (self.exact == other.exact || self.filters.is_empty() || other.filters.is_empty())
+    }
 
-        for i in (1..modules.len()).rev() {
-            let parts = modules
-                .clone()
-                .into_iter()
-                .take(i)
-                .chain(std::iter::once(super::MANIFEST_FILE.into()))
-                .collect::<Vec<String>>()
-                .join(path::MAIN_SEPARATOR.to_string().as_str());
-            if context.root().join(parts).exists() {
-                args.push("--package".to_string());
-                args.push(modules[i - 1].to_string());
-                modules.drain(0..i);
-                break;
+    fn merge(&mut self, other: Self) {
+        if self.filters.is_empty() || other.filters.is_empty() {
+            self.filters.clear();
+            self.exact = false;
+        } else {
+            for filter in other.filters {
+                if !self.filters.contains(&filter) {
+                    self.filters.push(filter);
+                }
             }
         }
+    }
+
+    fn into_args(self) -> ArgsList {
+        let mut filters = self.filters.into_iter();
+        let mut args = self.target_args;
+
+        // The first filter is passed by cargo to the test harness along with the rest
+        args.extend(filters.next());
 
-        if modules.len() <= 1 {
-            return Ok(args);
+        let rest: ArgsList = filters.collect();
+
+        if !rest.is_empty() || self.exact {
+            args.push(utils::EOO.into());
+            args.extend(rest);
         }
 
-        if modules.len() == 2 && modules[0] == "tests" {
-            return Ok(utils::concat(args, ["--test", &modules[1]]));
+        if self.exact {
+            args.push("--exact".into());
         }
 
-        let namespace = [&modules[1..], &["".into()]].concat().join(SEPARATOR);
-        Ok(utils::concat(args, [namespace]))
+        args
     }
+}
 
-    fn build_line_position_args(
-        &self,
-        context: &crate::Context,
-    ) -> Result<ArgsList, Box<dyn std::error::Error>> {
-        let mut args = self.build_file_position_args(context)?;
-        let nearest = self.find_nearest(context)?;
+impl Cargotest {
+    fn select_doctest(&self, context: &Context, item: Vec<String>) -> Result<Selection, Error> {
+        let location = super::find_location(context)?;
+        let mut args = vec![];
 
-        if !nearest.has_tests() || !Regex::new(r"#\[.*")?.is_match(&nearest.tests()[0]) {
-            return Ok(args);
+        if let Some(package) = &location.package {
+            args.extend(["--package".into(), package.clone()]);
         }
 
-        let forward_nearest = context.find_nearest(
-            &[self.forward_test_pattern.as_str().into()],
-            Default::default(),
-            nearest.line_nr().unwrap()..=context.line_nr().unwrap(),
-        )?;
-        let test_name = if nearest.namespaces().is_empty() {
-            forward_nearest.tests().first().unwrap().to_string()
-        } else if forward_nearest.tests().is_empty() {
-            nearest.tests().first().unwrap().to_string()
-        } else {
-            [&nearest.namespaces()[0..1], &forward_nearest.tests()[0..1]]
-                .concat()
-                .join(SEPARATOR)
+        // Doctests are only collected from the library, and `--doc` can't be mixed with `--lib`
+        args.push("--doc".into());
+
+        let path = [location.modules, item].concat();
+        let filter = (!path.is_empty()).then(|| path.join(super::SEPARATOR));
+
+        Ok(Selection::new(args, filter, false))
+    }
+
+    fn select_file(&self, context: &Context) -> Result<Selection, Error> {
+        let location = super::find_location(context)?;
+        let mut args = vec![];
+
+        if let Some(package) = &location.package {
+            args.extend(["--package".into(), package.clone()]);
+        }
+
+        if let Some(target) = &location.target {
+            args.extend(target.cargo_args());
+        }
+
+        let filter = (!location.modules.is_empty()).then(|| location.module_prefix());
+
+        Ok(Selection::new(args, filter, false))
+    }
+
+    fn select_line(&self, context: &Context) -> Result<Selection, Error> {
+        if let Some(item) = super::doctest::find_item(context)? {
+            return self.select_doctest(context, item);
+        }
+
+        let selection = self.select_file(context)?;
+        let nearest = self.find_nearest(context)?;
+        let Some(test_name) = self.nearest_test_name(context, &nearest)? else {
+            return Ok(selection);
         };
-        let file_namespace = if !args.is_empty() && args[0] != "--test" {
-            args.pop().unwrap_or_default()
-        } else {
-            String::new()
+        let location = super::find_location(context)?;
+        let path = format!(
+            "{}{}",
+            location.module_prefix(),
+            super::test_path(&nearest, test_name)
+        );
+        let (path, exact) = match Cases::find(context, &nearest, &self.forward_test_pattern)? {
+            Some(cases) => match context.case() {
+                Some(case) => ([path, cases.test_name(case)].join(super::SEPARATOR), true),
+                // The module of the parameterized test runs all of its cases
+                None => (path + super::SEPARATOR, false),
+            },
+            None => (path, true),
         };
 
-        Ok(args
-            .into_iter()
-            .chain(iter::once(format!("{}{}", file_namespace, test_name)))
-            .chain(iter::once("--".into()))
-            .chain(iter::once("--exact".into()))
-            .collect())
+        Ok(Selection::new(selection.target_args, Some(path), exact))
+    }
+
+    fn select(&self, context: &Context) -> Result<Selection, Error> {
+        match context.scope() {
+            Scope::Suite => Ok(Selection::default()),
+            Scope::File => self.select_file(context),
+            Scope::Line => self.select_line(context),
+        }
+    }
+}
+
+impl TestFramework for Cargotest {
+    fn supports_cases(&self) -> bool {
+        true
+    }
+
+    fn nearest_test_name(
+        &self,
+        context: &Context,
+        nearest: &Nearest,
+    ) -> Result<Option<String>, Error> {
+        super::nearest_test_name(context, nearest, &self.forward_test_pattern)
+    }
+
+    fn build_file_position_args(&self, context: &crate::Context) -> Result<ArgsList, Error> {
+        Ok(self.select_file(context)?.into_args())
+    }
+
+    fn build_line_position_args(&self, context: &crate::Context) -> Result<ArgsList, Error> {
+        Ok(self.select_line(context)?.into_args())
+    }
+
+    fn merge_position_args(&self, contexts: &[&Context]) -> Result<Vec<ArgsList>, Error> {
+        let mut selections: Vec<Selection> = vec![];
+
+        for context in contexts {
+            let selection = self.select(context)?;
+
+            // The whole workspace covers the rest of the locations
+            if matches!(context.scope(), Scope::Suite) {
+                return Ok(vec![selection.into_args()]);
+            }
+
+            match selections
+                .iter_mut()
+                .find(|other| other.can_merge(&selection))
+            {
+                Some(other) => other.merge(selection),
+                None => selections.push(selection),
+            }
+        }
+
+        Ok(selections.into_iter().map(Selection::into_args).collect())
     }
 }
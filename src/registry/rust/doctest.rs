@@ -0,0 +1,155 @@
+use crate::{Context, Error};
+use regex::Regex;
+
+const FENCE: &str = "```";
+
+/// When the line is inside a code block of a doc comment, returns the path of the item the
+/// comment documents relative to the file module, e.g. `["Foo", "bar"]` (empty for `//!`).
+pub(super) fn find_item(context: &Context) -> Result<Option<Vec<String>>, Error> {
+    let Some(line_nr) = context.line_nr() else {
+        return Ok(None);
+    };
+    let lines = context.lines(..)?.map(|(line, _)| line).collect::<Vec<_>>();
+    let Some(index) = line_nr.checked_sub(1).filter(|index| *index < lines.len()) else {
+        return Ok(None);
+    };
+    let Some(prefix) = doc_prefix(&lines[index]) else {
+        return Ok(None);
+    };
+
+    let fences_above = lines[..index]
+        .iter()
+        .rev()
+        .map_while(|line| doc_text(line, prefix))
+        .filter(|text| text.starts_with(FENCE))
+        .count();
+    let is_fence = doc_text(&lines[index], prefix).is_some_and(|text| text.starts_with(FENCE));
+
+    if !is_fence && fences_above % 2 == 0 {
+        return Ok(None);
+    }
+
+    // Inner doc comments document the module itself
+    if prefix == "//!" {
+        return Ok(Some(vec![]));
+    }
+
+    let Some((item_index, item)) = lines
+        .iter()
+        .enumerate()
+        .skip(index + 1)
+        .find(|(_, line)| {
+            let line = line.trim_start();
+
+            !line.is_empty() && !line.starts_with("///") && !line.starts_with("#[")
+        })
+        .and_then(|(item_index, line)| item_name(line).map(|item| (item_index, item)))
+    else {
+        return Ok(None);
+    };
+
+    let mut path = parents(&lines[..item_index], indent(&lines[item_index]))?;
+    path.push(item);
+
+    Ok(Some(path))
+}
+
+fn doc_prefix(line: &str) -> Option<&'static str> {
+    let line = line.trim_start();
+
+    ["///", "//!"]
+        .into_iter()
+        .find(|prefix| line.starts_with(prefix) && !line[prefix.len()..].starts_with('/'))
+}
+
+fn doc_text<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    line.trim_start()
+        .strip_prefix(prefix)
+        .map(|text| text.trim_start())
+}
+
+fn indent(line: &str) -> usize {
+    line.chars().take_while(|c| c.is_whitespace()).count()
+}
+
+fn item_name(line: &str) -> Option<String> {
+    let patterns = [
+        r#"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:(?:async|unsafe|const|extern\s+"[^"]*")\s+)*(?:fn|struct|enum|trait|mod|type|const|static|union)\s+(\w+)"#,
+        r"^\s*macro_rules!\s*(\w+)",
+        IMPL_PATTERN,
+    ];
+
+    patterns.into_iter().find_map(|pattern| {
+        Regex::new(pattern)
+            .ok()?
+            .captures(line)
+            .map(|captures| captures[1].to_string())
+    })
+}
+
+const IMPL_PATTERN: &str =
+    r"^\s*(?:unsafe\s+)?impl(?:<.*?>)?\s+(?:[^{]*?\s+for\s+)?(?:\w+::)*(\w+(?:<[^{]*>)?)";
+
+/// The names of the `impl`, `trait` and `mod` blocks enclosing the item, outermost first.
+fn parents(lines: &[String], item_indent: usize) -> Result<Vec<String>, Error> {
+    let parent_patterns = [
+        Regex::new(IMPL_PATTERN)?,
+        Regex::new(r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:unsafe\s+)?trait\s+(\w+)")?,
+        Regex::new(r"^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)\s*\{")?,
+    ];
+    let mut parents = vec![];
+    let mut min_indent = item_indent;
+
+    for line in lines.iter().rev() {
+        if min_indent == 0 {
+            break;
+        }
+
+        if line.trim().is_empty() || line.trim_start().starts_with("//") {
+            continue;
+        }
+
+        let indent = indent(line);
+
+        if indent >= min_indent {
+            continue;
+        }
+
+        min_indent = indent;
+
+        if let Some(captures) = parent_patterns
+            .iter()
+            .find_map(|pattern| pattern.captures(line))
+        {
+            parents.push(captures[1].trim().to_string());
+        }
+    }
+
+    parents.reverse();
+    Ok(parents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_name() {
+        assert_eq!(item_name("pub fn add(a: i32)"), Some("add".into()));
+        assert_eq!(
+            item_name("    pub(crate) const fn new() -> Self"),
+            Some("new".into())
+        );
+        assert_eq!(
+            item_name("pub const ANSWER: u8 = 42;"),
+            Some("ANSWER".into())
+        );
+        assert_eq!(item_name("pub unsafe trait Send {"), Some("Send".into()));
+        assert_eq!(item_name("macro_rules! vec {"), Some("vec".into()));
+        assert_eq!(
+            item_name("impl<T: Clone> Display for Foo<T> {"),
+            Some("Foo<T>".into())
+        );
+        assert_eq!(item_name("let x = 1;"), None);
+    }
+}
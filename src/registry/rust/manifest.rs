@@ -0,0 +1,162 @@
+use super::{Target, TargetKind};
+use crate::Error;
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The parts of `Cargo.toml` needed to resolve the targets of a package.
+#[derive(Deserialize, Default, Debug)]
+pub(super) struct Manifest {
+    package: Option<Package>,
+    workspace: Option<toml::Table>,
+    lib: Option<TargetConfig>,
+    #[serde(default)]
+    bin: Vec<TargetConfig>,
+    #[serde(default)]
+    test: Vec<TargetConfig>,
+    #[serde(default)]
+    bench: Vec<TargetConfig>,
+    #[serde(default)]
+    example: Vec<TargetConfig>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Package {
+    name: String,
+}
+
+#[derive(Deserialize, Default, Debug)]
+struct TargetConfig {
+    name: Option<String>,
+    path: Option<PathBuf>,
+}
+
+impl Manifest {
+    pub(super) fn load(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path)?;
+
+        toml::from_str(&content).map_err(|error: toml::de::Error| Error::Config {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        })
+    }
+
+    /// A workspace manifest without a package.
+    pub(super) fn is_virtual(&self) -> bool {
+        self.package.is_none() && self.workspace.is_some()
+    }
+
+    pub(super) fn package_name(&self) -> Option<&str> {
+        self.package.as_ref().map(|package| package.name.as_str())
+    }
+
+    /// The explicitly declared targets followed by the ones Cargo discovers automatically.
+    /// The paths are relative to the package directory.
+    pub(super) fn targets(&self, dir: &Path, package_name: &str) -> Vec<Target> {
+        let mut targets: Vec<Target> = vec![];
+        let mut add = |target: Target| {
+            if !targets.iter().any(|existing| existing.path == target.path) {
+                targets.push(target);
+            }
+        };
+
+        if let Some(lib) = &self.lib {
+            add(Target {
+                kind: TargetKind::Lib,
+                name: lib
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| package_name.replace('-', "_")),
+                path: lib.path.clone().unwrap_or_else(|| "src/lib.rs".into()),
+            });
+        }
+
+        for (kind, configs, dir_name) in [
+            (TargetKind::Bin, &self.bin, "src/bin"),
+            (TargetKind::Test, &self.test, "tests"),
+            (TargetKind::Bench, &self.bench, "benches"),
+            (TargetKind::Example, &self.example, "examples"),
+        ] {
+            for config in configs {
+                let Some(name) = config.name.clone() else {
+                    continue;
+                };
+                let path = config.path.clone().unwrap_or_else(|| {
+                    let candidates = [
+                        Path::new(dir_name).join(format!("{}.rs", name)),
+                        Path::new(dir_name).join(&name).join("main.rs"),
+                    ];
+
+                    candidates
+                        .iter()
+                        .find(|path| dir.join(path).is_file())
+                        .unwrap_or(&candidates[0])
+                        .clone()
+                });
+
+                add(Target { kind, name, path });
+            }
+        }
+
+        if dir.join("src/lib.rs").is_file() {
+            add(Target {
+                kind: TargetKind::Lib,
+                name: package_name.replace('-', "_"),
+                path: "src/lib.rs".into(),
+            });
+        }
+
+        if dir.join("src/main.rs").is_file() {
+            add(Target {
+                kind: TargetKind::Bin,
+                name: package_name.to_string(),
+                path: "src/main.rs".into(),
+            });
+        }
+
+        for (kind, dir_name) in [
+            (TargetKind::Bin, "src/bin"),
+            (TargetKind::Test, "tests"),
+            (TargetKind::Bench, "benches"),
+            (TargetKind::Example, "examples"),
+        ] {
+            for (name, path) in discover(dir, dir_name) {
+                add(Target { kind, name, path });
+            }
+        }
+
+        targets
+    }
+}
+
+/// Finds `<dir>/*.rs` and `<dir>/*/main.rs` targets, sorted by name.
+fn discover(package_dir: &Path, dir_name: &str) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(package_dir.join(dir_name)) else {
+        return vec![];
+    };
+    let mut targets = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let file_name = entry.file_name().to_str()?.to_string();
+
+            if path.is_file() {
+                let name = file_name.strip_suffix(".rs")?;
+
+                Some((name.to_string(), Path::new(dir_name).join(&file_name)))
+            } else if path.join("main.rs").is_file() {
+                Some((
+                    file_name.clone(),
+                    Path::new(dir_name).join(&file_name).join("main.rs"),
+                ))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    targets.sort();
+    targets
+}
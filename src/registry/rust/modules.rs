@@ -0,0 +1,89 @@
+use regex::Regex;
+use std::{
+    collections::{HashSet, VecDeque},
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+/// Resolves the module path of the file by following the `mod foo;` declarations (and their
+/// `#[path]` attributes) from the crate root. The paths are relative to the package directory.
+pub(super) fn resolve(package_dir: &Path, crate_root: &Path, file: &Path) -> Option<Vec<String>> {
+    let declaration_pattern = Regex::new(r"^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)\s*;").ok()?;
+    let path_pattern = Regex::new(r#"^\s*#\[path\s*=\s*"([^"]+)"\]"#).ok()?;
+    let mut queue = VecDeque::from([(crate_root.to_path_buf(), vec![], true)]);
+    let mut visited = HashSet::new();
+
+    while let Some((current, modules, is_mod_rs)) = queue.pop_front() {
+        if !visited.insert(current.clone()) {
+            continue;
+        }
+
+        let Ok(content) = fs::read_to_string(package_dir.join(&current)) else {
+            continue;
+        };
+        let dir = current.parent().unwrap_or(Path::new("")).to_path_buf();
+        let mut path_attribute = None;
+
+        for line in content.lines() {
+            if let Some(captures) = path_pattern.captures(line) {
+                path_attribute = Some(captures[1].to_string());
+                continue;
+            }
+
+            if let Some(captures) = declaration_pattern.captures(line) {
+                let name = captures[1].to_string();
+                let child = match path_attribute.take() {
+                    // Files loaded with `#[path]` behave like `mod.rs` files
+                    Some(path) => Some((normalize(&dir.join(path)), true)),
+                    None => {
+                        // `foo.rs` declares its modules in `foo/`, `mod.rs` (and the crate root)
+                        // in its own directory
+                        let base = if is_mod_rs {
+                            dir.clone()
+                        } else {
+                            dir.join(current.file_stem().unwrap_or_default())
+                        };
+
+                        [
+                            (base.join(format!("{}.rs", name)), false),
+                            (base.join(&name).join("mod.rs"), true),
+                        ]
+                        .into_iter()
+                        .find(|(path, _)| package_dir.join(path).is_file())
+                    }
+                };
+                let Some((child, is_mod_rs)) = child else {
+                    continue;
+                };
+                let child_modules = [&modules[..], &[name]].concat();
+
+                if child == file {
+                    return Some(child_modules);
+                }
+
+                queue.push_back((child, child_modules, is_mod_rs));
+            } else if !line.trim_start().starts_with("#[") {
+                path_attribute = None;
+            }
+        }
+    }
+
+    None
+}
+
+/// Resolves `..` and `.` without touching the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
@@ -0,0 +1,132 @@
+use super::{parameterized::Cases, Rust, TargetKind};
+use crate::{
+    alternate::Projection,
+    context::Nearest,
+    test_framework::{self, TestFramework, TestFrameworkMeta},
+    utils, ArgsList, Context, Error,
+};
+use smart_default::SmartDefault;
+
+const CONFIG_FILE: &str = ".config/nextest.toml";
+
+#[derive(TestFrameworkMeta, SmartDefault)]
+pub struct Nextest {
+    language: Rust,
+    #[default(_code = "super::PATTERN.into()")]
+    pattern: String,
+    #[default(_code = r#"vec!["cargo".into(), "nextest".into(), "run".into()]"#)]
+    executable: Vec<String>,
+    args: Vec<String>,
+    #[default(_code = "super::TEST_PATTERN.into()")]
+    test_pattern: String,
+    #[default(_code = "super::NAMESPACE_PATTERN.into()")]
+    namespace_pattern: String,
+    projections: Vec<Projection>,
+    // Opted in with `enabled = true` when there is no nextest config
+    #[enabled]
+    enabled: bool,
+
+    #[default(_code = "super::FORWARD_TEST_PATTERN.into()")]
+    forward_test_pattern: String,
+}
+
+/// Selects the tests by their path relative to the module of the file.
+enum TestFilter {
+    Exact(String),
+    /// All the cases of a parameterized test.
+    Prefix(String),
+}
+
+impl Nextest {
+    fn build_filterset(
+        &self,
+        context: &Context,
+        test_filter: Option<TestFilter>,
+    ) -> Result<ArgsList, Error> {
+        let location = super::find_location(context)?;
+        let mut filters = vec![];
+
+        if let Some(package) = &location.package {
+            filters.push(format!("package({})", package));
+        }
+
+        if let Some(target) = &location.target {
+            filters.push(format!("kind({})", target.kind.name()));
+
+            if target.kind != TargetKind::Lib {
+                filters.push(format!("binary(={})", target.name));
+            }
+        }
+
+        match test_filter {
+            Some(TestFilter::Exact(test_name)) => {
+                filters.push(format!("test(={}{})", location.module_prefix(), test_name))
+            }
+            Some(TestFilter::Prefix(test_name)) => filters.push(format!(
+                "test(/^{}/)",
+                regex::escape(&format!("{}{}", location.module_prefix(), test_name))
+            )),
+            None if !location.modules.is_empty() => {
+                filters.push(format!(
+                    "test(/^{}/)",
+                    regex::escape(&location.module_prefix())
+                ));
+            }
+            None => {}
+        }
+
+        if filters.is_empty() {
+            Ok(vec![])
+        } else {
+            Ok(vec!["-E".into(), filters.join(" & ")])
+        }
+    }
+}
+
+impl TestFramework for Nextest {
+    fn supports_cases(&self) -> bool {
+        true
+    }
+
+    fn is_suitable_for(&self, context: &Context) -> bool {
+        self.matches_pattern(context)
+            && (context.find_file(CONFIG_FILE).is_some()
+                || (self.enabled && utils::is_executable("cargo-nextest")))
+    }
+
+    fn nearest_test_name(
+        &self,
+        context: &Context,
+        nearest: &Nearest,
+    ) -> Result<Option<String>, Error> {
+        super::nearest_test_name(context, nearest, &self.forward_test_pattern)
+    }
+
+    fn build_file_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
+        self.build_filterset(context, None)
+    }
+
+    fn build_line_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
+        let nearest = self.find_nearest(context)?;
+        let Some(test_name) = self.nearest_test_name(context, &nearest)? else {
+            return self.build_filterset(context, None);
+        };
+        let path = super::test_path(&nearest, test_name);
+        let filter = match Cases::find(context, &nearest, &self.forward_test_pattern)? {
+            Some(cases) => match context.case() {
+                Some(case) => {
+                    TestFilter::Exact([path, cases.test_name(case)].join(super::SEPARATOR))
+                }
+                None => TestFilter::Prefix(path + super::SEPARATOR),
+            },
+            None => TestFilter::Exact(path),
+        };
+
+        self.build_filterset(context, Some(filter))
+    }
+
+    fn merge_position_args(&self, contexts: &[&Context]) -> Result<Vec<ArgsList>, Error> {
+        // Several filtersets select the tests matching any of them
+        test_framework::concat_position_args(self, contexts)
+    }
+}
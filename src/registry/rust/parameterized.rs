@@ -0,0 +1,179 @@
+use crate::{context::Nearest, Context, Error, LineNr};
+use regex::Regex;
+
+/// The cases of a parameterized test. The test function expands into a module named after it
+/// with a test per case, so the module path runs all of them.
+#[derive(Debug, PartialEq)]
+pub(super) enum Cases {
+    /// `rstest` cases along with their descriptions, e.g. `#[case::negative(-1)]`.
+    Rstest(Vec<Option<String>>),
+    /// The names of the tests generated by `test_case`.
+    TestCase(Vec<String>),
+}
+
+impl Cases {
+    /// Finds the cases in the attributes of the nearest test, if it is parameterized.
+    pub(super) fn find(
+        context: &Context,
+        nearest: &Nearest,
+        forward_test_pattern: &str,
+    ) -> Result<Option<Self>, Error> {
+        let Some(line_nr) = nearest.line_nr() else {
+            return Ok(None);
+        };
+        let Some(fn_line_nr) = context
+            .find_nearest(
+                &[forward_test_pattern.into()],
+                Default::default(),
+                line_nr..,
+            )?
+            .line_nr()
+        else {
+            return Ok(None);
+        };
+
+        Ok(Self::parse(&attributes(context, fn_line_nr)?))
+    }
+
+    fn parse(attributes: &str) -> Option<Self> {
+        if attributes.contains("#[rstest") {
+            let case = Regex::new(r"\bcase(?:::(\w+))?\s*\(").unwrap();
+            let cases: Vec<_> = case
+                .captures_iter(attributes)
+                .map(|captures| captures.get(1).map(|m| m.as_str().to_string()))
+                .collect();
+
+            // Without cases and values it is a plain test that only uses fixtures
+            if cases.is_empty() && !attributes.contains("#[values") {
+                None
+            } else {
+                Some(Self::Rstest(cases))
+            }
+        } else {
+            let test_case = Regex::new(r"(?m)^\s*#\[(?:test_case::)?test_case\((.*)\)\]").unwrap();
+            let names: Vec<_> = test_case
+                .captures_iter(attributes)
+                .map(|captures| test_case_name(&captures[1]))
+                .collect();
+
+            if names.is_empty() {
+                None
+            } else {
+                Some(Self::TestCase(names))
+            }
+        }
+    }
+
+    /// The name of the test generated for the case, picked either by its 1-based index or
+    /// by the name itself.
+    pub(super) fn test_name(&self, case: &str) -> String {
+        let index = case.parse::<usize>().ok().filter(|index| *index > 0);
+
+        match (self, index) {
+            (Self::Rstest(cases), Some(index)) if index <= cases.len() => {
+                // The index is zero-padded to the width of the number of cases
+                let width = cases.len().to_string().len();
+
+                match &cases[index - 1] {
+                    Some(description) => format!("case_{:0width$}_{}", index, description),
+                    None => format!("case_{:0width$}", index),
+                }
+            }
+            (Self::TestCase(names), Some(index)) if index <= names.len() => {
+                names[index - 1].clone()
+            }
+            _ => case.to_string(),
+        }
+    }
+}
+
+/// The attributes above the function, up to the previous item.
+fn attributes(context: &Context, fn_line_nr: LineNr) -> Result<String, Error> {
+    if fn_line_nr <= 1 {
+        return Ok(String::new());
+    }
+
+    let mut lines: Vec<_> = context
+        .lines(fn_line_nr - 1..=1)?
+        .map(|(line, _)| line)
+        .take_while(|line| {
+            let line = line.trim();
+            !line.is_empty() && !line.ends_with(['}', ';', '{'])
+        })
+        .collect();
+    lines.reverse();
+
+    Ok(lines.join("\n"))
+}
+
+/// The name `test_case` generates from the description, or from the arguments without one.
+fn test_case_name(arguments: &str) -> String {
+    let description = Regex::new(r#";\s*"(.*)"\s*$"#).unwrap();
+    let name = match description.captures(arguments) {
+        Some(captures) => captures[1].to_string(),
+        None => arguments.replace("=>", " expects "),
+    };
+    let mut escaped = String::new();
+
+    for c in name.to_lowercase().chars() {
+        if c.is_alphanumeric() {
+            escaped.push(c);
+        } else if !escaped.ends_with('_') {
+            escaped.push('_');
+        }
+    }
+
+    if escaped.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        escaped
+    } else {
+        format!("_{}", escaped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Cases::parse("#[test]"), None);
+        assert_eq!(Cases::parse("#[rstest]"), None);
+        assert_eq!(
+            Cases::parse("#[rstest]\n#[case(1)]\n#[case::two(2)]"),
+            Some(Cases::Rstest(vec![None, Some("two".into())]))
+        );
+        assert_eq!(
+            Cases::parse("#[rstest(input,\n    case(1),\n    case(2),\n)]"),
+            Some(Cases::Rstest(vec![None, None]))
+        );
+        assert_eq!(
+            Cases::parse(
+                "#[test_case(-2, -4 ; \"when both are negative\")]\n#[test_case(2, 4 => 8)]"
+            ),
+            Some(Cases::TestCase(vec![
+                "when_both_are_negative".into(),
+                "_2_4_expects_8".into()
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_test_name() {
+        let cases = Cases::Rstest(
+            vec![None; 9]
+                .into_iter()
+                .chain([Some("ten".into())])
+                .collect(),
+        );
+
+        assert_eq!(cases.test_name("1"), "case_01");
+        assert_eq!(cases.test_name("10"), "case_10_ten");
+        assert_eq!(cases.test_name("11"), "11");
+        assert_eq!(cases.test_name("case_02"), "case_02");
+
+        let cases = Cases::TestCase(vec!["one".into()]);
+
+        assert_eq!(cases.test_name("1"), "one");
+        assert_eq!(cases.test_name("two"), "two");
+    }
+}
@@ -7,3 +7,6 @@ mod zigtest;
 
 #[derive(Language, Default)]
 struct Zig {}
+
+/// A Zig project, `zig build` runs from the directory of `build.zig`.
+const ROOT_MARKERS: [&str; 1] = ["build.zig"];
@@ -1,7 +1,8 @@
 use super::Zig;
 use crate::{
+    alternate::Projection,
     test_framework::{TestFramework, TestFrameworkMeta},
-    utils, ArgsList, Context,
+    utils, ArgsList, Context, Error,
 };
 use smart_default::SmartDefault;
 
@@ -10,33 +11,29 @@ pub struct Zigtest {
     language: Zig,
     #[default = r".zig$"]
     pattern: String,
-    #[default(_code = r#"vec!["zig"]"#)]
-    executable: Vec<&'static str>,
-    args: Vec<&'static str>,
+    #[default(_code = r#"vec!["zig".into()]"#)]
+    executable: Vec<String>,
+    args: Vec<String>,
     #[default = r#"^\s*test\s+"(.+)""#]
     test_pattern: String,
     namespace_pattern: String,
+    projections: Vec<Projection>,
 }
 
 impl TestFramework for Zigtest {
-    fn build_suite_position_args(
-        &self,
-        _context: &Context,
-    ) -> Result<ArgsList, Box<dyn std::error::Error>> {
+    fn root_markers(&self) -> &[&str] {
+        &super::ROOT_MARKERS
+    }
+
+    fn build_suite_position_args(&self, _context: &Context) -> Result<ArgsList, Error> {
         Ok(vec!["build".into(), "test".into()])
     }
 
-    fn build_file_position_args(
-        &self,
-        context: &Context,
-    ) -> Result<ArgsList, Box<dyn std::error::Error>> {
+    fn build_file_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
         Ok(vec!["test".into(), context.rel_str().into()])
     }
 
-    fn build_line_position_args(
-        &self,
-        context: &Context,
-    ) -> Result<ArgsList, Box<dyn std::error::Error>> {
+    fn build_line_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
         let args = self.build_file_position_args(context)?;
         let nearest = self.find_nearest(context)?;
 
@@ -45,7 +42,7 @@ impl TestFramework for Zigtest {
         };
 
         let filter = nearest.tests().join("");
-        let line_args = utils::concat(["--test-filter"], [utils::quote(&filter)]);
+        let line_args = utils::concat(["--test-filter"], [filter]);
 
         Ok(utils::concat(args, line_args))
     }
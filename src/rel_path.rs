@@ -1,10 +1,9 @@
 use rev_buf_reader::RevBufReader;
 
-use crate::LineNr;
-use std::error::Error;
+use crate::{Error, LineNr};
 use std::fs::File;
 use std::mem;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::{
     env,
     io::{self, BufRead, Seek},
@@ -21,14 +20,14 @@ struct LRange {
 impl LRange {
     const MIN: u8 = 1;
 
-    fn new(start: LineNr, step: isize, end: Option<LineNr>) -> Result<Self, Box<dyn Error>> {
+    fn new(start: LineNr, step: isize, end: Option<LineNr>) -> Result<Self, Error> {
         if start < Self::MIN.into() {
-            return Err("`start` isn't 1-based".into());
+            return Err(Error::Other("`start` isn't 1-based".into()));
         }
 
         if let Some(end) = end {
             if end < Self::MIN.into() {
-                return Err("`end` isn't 1-based".into());
+                return Err(Error::Other("`end` isn't 1-based".into()));
             }
         }
 
@@ -37,7 +36,7 @@ impl LRange {
 
     fn try_from_range<T: Into<LineNr> + Clone>(
         value: &impl ops::RangeBounds<T>,
-    ) -> Result<Self, Box<dyn Error>> {
+    ) -> Result<Self, Error> {
         let start: LineNr = match value.start_bound() {
             ops::Bound::Included(start) => start.clone().into(),
             ops::Bound::Excluded(start) => start.clone().into(),
@@ -106,9 +105,9 @@ impl Iterator for LRange {
     }
 }
 
-type LineWithNr = (String, LineNr);
+pub type LineWithNr = (String, LineNr);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RelPath {
     root: PathBuf,
     path: PathBuf,
@@ -116,7 +115,7 @@ pub struct RelPath {
 }
 
 impl RelPath {
-    pub fn new(root: Option<&str>, path: &str) -> Result<Self, Box<dyn Error>> {
+    pub fn new(root: Option<&str>, path: &str) -> Result<Self, Error> {
         let mut rel_root: Option<PathBuf> = None;
         let mut root = if let Some(root) = root {
             PathBuf::from(root)
@@ -130,7 +129,7 @@ impl RelPath {
         }
 
         if !root.is_dir() {
-            return Err("Root path must be an existing directory".into());
+            return Err(Error::InvalidRoot(root));
         }
 
         let mut path = PathBuf::from(path);
@@ -147,14 +146,13 @@ impl RelPath {
         }
 
         if !path.exists() {
-            return Err("Path does not exist".into());
+            return Err(Error::PathNotFound(path));
         }
 
-        let rel_path = path
-            .clone()
-            .strip_prefix(&root)
-            .map_err(|_| "Path must be a subpath of the root path")?
-            .to_path_buf();
+        let rel_path = match path.strip_prefix(&root) {
+            Ok(rel_path) => rel_path.to_path_buf(),
+            Err(_) => return Err(Error::PathOutsideRoot { path, root }),
+        };
 
         Ok(Self {
             root,
@@ -180,7 +178,7 @@ impl RelPath {
     }
 
     /// Opens the file and advances to the passed line.
-    pub fn open(&self, line: LineNr) -> Result<io::BufReader<File>, io::Error> {
+    pub fn open(&self, line: LineNr) -> Result<io::BufReader<File>, Error> {
         let file = File::open(self.path())?;
         let mut buf_reader = io::BufReader::new(file);
         let mut current_line = 1;
@@ -188,10 +186,10 @@ impl RelPath {
 
         while current_line < line {
             if buf_reader.read_line(&mut buf)? == 0 {
-                return Err(io::Error::new(
-                    io::ErrorKind::UnexpectedEof,
-                    format!("Line #{} not found", line),
-                ));
+                return Err(Error::InvalidLine {
+                    path: self.path.clone(),
+                    line_nr: line,
+                });
             }
             buf.clear();
             current_line += 1;
@@ -203,9 +201,18 @@ impl RelPath {
     pub fn lines(
         &self,
         range: impl ops::RangeBounds<LineNr>,
-    ) -> Result<Box<dyn Iterator<Item = LineWithNr>>, Box<dyn Error>> {
+    ) -> Result<Box<dyn Iterator<Item = LineWithNr>>, Error> {
         let numbers: LRange = LRange::try_from_range(&range)?;
-        let mut buffer = self.open(numbers.forward_to())?;
+        // A reverse range reads past its start, so report the requested line instead
+        let mut buffer = self
+            .open(numbers.forward_to())
+            .map_err(|error| match error {
+                Error::InvalidLine { path, .. } => Error::InvalidLine {
+                    path,
+                    line_nr: numbers.start(),
+                },
+                error => error,
+            })?;
 
         let lines: Box<dyn Iterator<Item = Result<String, io::Error>>> = if numbers.is_desc() {
             let position = buffer.stream_position()?;
@@ -221,8 +228,31 @@ impl RelPath {
         Ok(Box::new(lines.map(Result::unwrap_or_default).zip(numbers)))
     }
 
-    pub fn file(&self, path: &str) -> Result<Self, Box<dyn Error>> {
-        Self::new(Some(self.root().to_str().ok_or("Invalid root path")?), path)
+    pub fn file(&self, path: &str) -> Result<Self, Error> {
+        let root = self
+            .root()
+            .to_str()
+            .ok_or_else(|| Error::InvalidRoot(self.root().clone()))?;
+
+        Self::new(Some(root), path)
+    }
+
+    /// Returns the same path relative to another root, one of its ancestors.
+    pub fn with_root(&self, root: &Path) -> Result<Self, Error> {
+        let rel = self
+            .path
+            .strip_prefix(root)
+            .map_err(|_| Error::PathOutsideRoot {
+                path: self.path.clone(),
+                root: root.to_path_buf(),
+            })?
+            .to_path_buf();
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            path: self.path.clone(),
+            rel,
+        })
     }
 }
 
@@ -231,8 +261,8 @@ mod tests {
     use super::*;
     use std::env;
 
-    fn rel_path_error(root: &str, path: &str) -> String {
-        RelPath::new(Some(root), path).unwrap_err().to_string()
+    fn rel_path_error(root: &str, path: &str) -> Error {
+        RelPath::new(Some(root), path).unwrap_err()
     }
 
     #[test]
@@ -247,40 +277,40 @@ mod tests {
             .unwrap()
             .to_string();
 
-        assert_eq!(
+        assert!(matches!(
             rel_path_error("tests/fixtures/folder/file.txt", ""),
-            "Root path must be an existing directory"
-        );
+            Error::InvalidRoot(_)
+        ));
 
-        assert_eq!(
+        assert!(matches!(
             rel_path_error("/tmp/s0me_f0lDer", ""),
-            "Root path must be an existing directory"
-        );
+            Error::InvalidRoot(_)
+        ));
 
-        assert_eq!(
+        assert!(matches!(
             rel_path_error(folder, "non_existent.rs"),
-            "Path does not exist"
-        );
+            Error::PathNotFound(path) if path.ends_with("non_existent.rs")
+        ));
 
-        assert_eq!(
+        assert!(matches!(
             rel_path_error(folder, &other_file),
-            "Path must be a subpath of the root path"
-        );
+            Error::PathOutsideRoot { .. }
+        ));
 
         let rel_path = RelPath::new(Some(folder), "file.txt").unwrap();
 
-        assert!(rel_path.root().ends_with(&folder));
+        assert!(rel_path.root().ends_with(folder));
         assert!(rel_path.path().ends_with(&rel));
         assert_eq!(*rel_path.rel(), PathBuf::from(file));
 
         let rel_path = RelPath::new(Some(folder), &rel).unwrap();
 
-        assert!(rel_path.root().ends_with(&folder));
+        assert!(rel_path.root().ends_with(folder));
         assert!(rel_path.path().ends_with(&rel));
         assert_eq!(*rel_path.rel(), PathBuf::from(file));
     }
 
-    fn read_line(line: LineNr) -> Result<String, Box<dyn Error>> {
+    fn read_line(line: LineNr) -> Result<String, Error> {
         let mut buf_reader = RelPath::new(Some("tests/fixtures/folder"), "file.txt")
             .unwrap()
             .open(line)?;
@@ -296,10 +326,10 @@ mod tests {
         assert_eq!(read_line(10).unwrap(), "");
 
         let error = read_line(11).unwrap_err();
-        assert_eq!(error.to_string(), "Line #11 not found");
+        assert!(matches!(error, Error::InvalidLine { line_nr: 11, .. }));
     }
 
-    fn get_lines(range: impl ops::RangeBounds<LineNr>) -> Result<Vec<LineWithNr>, Box<dyn Error>> {
+    fn get_lines(range: impl ops::RangeBounds<LineNr>) -> Result<Vec<LineWithNr>, Error> {
         let lines = RelPath::new(Some("tests/fixtures/folder"), "file.txt")?
             .lines(range)?
             .collect::<Vec<LineWithNr>>();
@@ -308,6 +338,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::reversed_empty_ranges)]
     fn test_rel_path_lines() {
         assert_eq!(
             get_lines(..).unwrap(),
@@ -378,6 +409,9 @@ mod tests {
 
         assert_eq!(get_lines(1..=1).unwrap(), vec![(String::from("line1"), 1)]);
 
+        let error = get_lines(11..=1).unwrap_err();
+        assert!(matches!(error, Error::InvalidLine { line_nr: 11, .. }));
+
         let error = get_lines(0..5).unwrap_err();
         assert_eq!(error.to_string(), "`start` isn't 1-based");
 
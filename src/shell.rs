@@ -0,0 +1,109 @@
+use clap::ValueEnum;
+use std::{ffi::OsStr, process::Command};
+
+/// The shell a command is rendered for, so it can be pasted into it verbatim.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Shell {
+    Posix,
+    #[value(name = "powershell")]
+    PowerShell,
+}
+
+impl Default for Shell {
+    fn default() -> Self {
+        if cfg!(windows) {
+            Self::PowerShell
+        } else {
+            Self::Posix
+        }
+    }
+}
+
+impl Shell {
+    pub fn quote(&self, arg: &str) -> String {
+        match self {
+            Self::Posix => match shlex::try_quote(arg) {
+                Ok(quoted) if quoted == arg => arg.to_string(),
+                // shlex splits the quotes around some characters (e.g. `a'/'^b`) which is
+                // valid but hard to read, so wrap the whole argument when possible
+                Ok(_) if !arg.contains('\'') => format!("'{}'", arg),
+                Ok(quoted) => quoted.to_string(),
+                Err(_) => {
+                    log::warn!("Cannot quote an argument containing a nul byte");
+                    String::new()
+                }
+            },
+            Self::PowerShell => {
+                let is_safe = !arg.is_empty()
+                    && arg
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || "_-./\\:=+%".contains(c));
+
+                if is_safe {
+                    arg.to_string()
+                } else {
+                    format!("'{}'", arg.replace('\'', "''"))
+                }
+            }
+        }
+    }
+
+    pub fn format(&self, command: &Command) -> String {
+        let program = lossy(command.get_program());
+        let quoted_program = self.quote(&program);
+        let mut parts = vec![
+            // PowerShell treats a quoted string as an expression unless it is invoked
+            if *self == Self::PowerShell && quoted_program != program {
+                format!("& {}", quoted_program)
+            } else {
+                quoted_program
+            },
+        ];
+
+        parts.extend(command.get_args().map(|arg| self.quote(&lossy(arg))));
+        parts.join(" ")
+    }
+}
+
+fn lossy(value: &OsStr) -> String {
+    value.to_string_lossy().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(Shell::Posix.quote("file.rs"), "file.rs");
+        assert_eq!(Shell::Posix.quote("a b"), "'a b'");
+        assert_eq!(Shell::Posix.quote("it's"), r#""it's""#);
+        assert_eq!(Shell::Posix.quote(""), "''");
+        assert_eq!(Shell::Posix.quote("^a$/^b$"), "'^a$/^b$'");
+
+        assert_eq!(Shell::PowerShell.quote(r"src\file.rs"), r"src\file.rs");
+        assert_eq!(Shell::PowerShell.quote("a b"), "'a b'");
+        assert_eq!(Shell::PowerShell.quote("it's"), "'it''s'");
+        assert_eq!(Shell::PowerShell.quote("$env"), "'$env'");
+        assert_eq!(Shell::PowerShell.quote(""), "''");
+    }
+
+    #[test]
+    fn test_shell_format() {
+        let mut command = Command::new("my jest");
+        command
+            .arg("-t")
+            .arg("^Math Addition$")
+            .arg("--")
+            .arg("a.js");
+
+        assert_eq!(
+            Shell::Posix.format(&command),
+            "'my jest' -t '^Math Addition$' -- a.js"
+        );
+        assert_eq!(
+            Shell::PowerShell.format(&command),
+            "& 'my jest' -t '^Math Addition$' -- a.js"
+        );
+    }
+}
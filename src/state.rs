@@ -0,0 +1,168 @@
+use crate::cli::CliError;
+use anytest::{Context, LineNr, Scope};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    env,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+const STATE_DIR: &str = "anytest";
+const STATE_FILE: &str = "state.toml";
+
+/// A test position that can be turned back into a `Context`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Position {
+    rel: PathBuf,
+    line_nr: Option<LineNr>,
+    scope: Scope,
+    framework: Option<String>,
+    #[serde(default)]
+    case: Option<String>,
+}
+
+impl Position {
+    pub fn to_context(&self, root: &Path) -> Result<Context, Box<dyn Error>> {
+        let context = Context::new(
+            Some(
+                root.to_str()
+                    .ok_or_else(|| CliError::InvalidPath(root.display().to_string()))?,
+            ),
+            self.rel
+                .to_str()
+                .ok_or_else(|| CliError::InvalidPath(self.rel.display().to_string()))?,
+            self.line_nr,
+            Some(self.scope.clone()),
+            self.framework.as_deref(),
+        )?;
+
+        Ok(context.with_case(self.case.as_deref()))
+    }
+}
+
+impl From<&Context> for Position {
+    fn from(context: &Context) -> Self {
+        Self {
+            rel: context.rel().clone(),
+            line_nr: context.line_nr(),
+            scope: context.scope().clone(),
+            framework: context.framework().map(|s| s.to_string()),
+            case: context.case().map(|s| s.to_string()),
+        }
+    }
+}
+
+/// Positions of the last executed tests, keyed by the root directory.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct State {
+    #[serde(default)]
+    last: HashMap<String, Position>,
+}
+
+impl State {
+    /// `$XDG_CACHE_HOME/anytest/state.toml` (`~/.cache/anytest/state.toml` by default).
+    pub fn path() -> Option<PathBuf> {
+        let dir = env::var_os("XDG_CACHE_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+
+        Some(dir.join(STATE_DIR).join(STATE_FILE))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+
+        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, toml::to_string(self)?)?;
+
+        Ok(())
+    }
+
+    pub fn last(&self, root: &Path) -> Option<&Position> {
+        self.last.get(&key(root))
+    }
+
+    pub fn set_last(&mut self, context: &Context) {
+        self.last.insert(key(context.root()), context.into());
+    }
+}
+
+fn key(root: &Path) -> String {
+    fs::canonicalize(root)
+        .unwrap_or_else(|_| root.to_path_buf())
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Remembers the context as the last executed one, failures are only logged.
+pub fn save_last(context: &Context) {
+    let Some(path) = State::path() else {
+        return;
+    };
+
+    let result = State::load(&path).and_then(|mut state| {
+        state.set_last(context);
+        state.save(&path)
+    });
+
+    if let Err(error) = result {
+        log::warn!("Cannot save the last test: {}", error);
+    }
+}
+
+/// Restores the context of the last executed test in the root directory.
+pub fn load_last(root: &Path) -> Result<Context, Box<dyn Error>> {
+    let path = State::path().ok_or(CliError::NoStateFile)?;
+    let state = State::load(&path)?;
+    let position = state.last(root).ok_or(CliError::NoLastTest)?;
+
+    position.to_context(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_last() {
+        let root = env::current_dir().unwrap().join("tests/fixtures/rspec");
+        let path = env::temp_dir().join("anytest-test-state").join(STATE_FILE);
+        let context = Context::new(
+            Some(root.to_str().unwrap()),
+            "normal_spec.rb",
+            Some(3),
+            None,
+            Some("rspec"),
+        )
+        .unwrap();
+
+        let mut state = State::default();
+        state.set_last(&context);
+        state.save(&path).unwrap();
+
+        let state = State::load(&path).unwrap();
+        let position = state.last(&root).unwrap();
+
+        assert_eq!(position, &Position::from(&context));
+        assert!(state.last(&env::temp_dir()).is_none());
+
+        let context = position.to_context(&root).unwrap();
+
+        assert_eq!(context.rel(), &PathBuf::from("normal_spec.rb"));
+        assert_eq!(context.line_nr(), Some(3));
+        assert!(matches!(context.scope(), Scope::Line));
+        assert_eq!(context.framework(), Some("rspec"));
+    }
+}
@@ -0,0 +1,408 @@
+//! Finds the nearest test by parsing the file with tree-sitter, for the languages whose grammar
+//! is compiled in (see the `tree-sitter-*` features).
+
+use crate::{context::Nearest, named_pattern::NamedPattern, Context, Error};
+
+/// Returns `None` when there is no grammar for the language, so the caller can fall back to
+/// the regex-based search.
+#[cfg_attr(not(feature = "tree-sitter"), allow(unused_variables))]
+pub fn find_nearest(
+    context: &Context,
+    language: &str,
+    test_patterns: &[NamedPattern],
+) -> Result<Option<Nearest>, Error> {
+    #[cfg(feature = "tree-sitter")]
+    if let Some(grammar) = backend::Grammar::find(context, language) {
+        return grammar.find_nearest(context, test_patterns).map(Some);
+    }
+
+    Ok(None)
+}
+
+#[cfg(feature = "tree-sitter")]
+mod backend {
+    use crate::{context::Nearest, named_pattern::NamedPattern, Context, Error};
+    use std::fs;
+    use tree_sitter::{Language, Node, Parser, Query, QueryCursor, StreamingIterator};
+
+    #[cfg(feature = "tree-sitter-rust")]
+    const RUST_QUERY: &str = r#"
+        (mod_item name: (identifier) @name) @namespace
+        (function_item name: (identifier) @name) @test
+    "#;
+
+    #[cfg(feature = "tree-sitter-python")]
+    const PYTHON_QUERY: &str = r#"
+        (class_definition name: (identifier) @name) @namespace
+        (function_definition name: (identifier) @name) @test
+    "#;
+
+    // Matches `describe("...")`, `describe.only("...")` and `describe.each(...)("...")`
+    #[cfg(feature = "tree-sitter-javascript")]
+    const JAVASCRIPT_QUERY: &str = r#"
+        (call_expression
+          function: [
+            (identifier) @function
+            (member_expression object: (identifier) @function)
+            (call_expression function: (member_expression object: (identifier) @function))
+          ]
+          arguments: (arguments . [(string) (template_string)] @name)
+          (#match? @function "^(describe|context|suite)$")) @namespace
+        (call_expression
+          function: [
+            (identifier) @function
+            (member_expression object: (identifier) @function)
+            (call_expression function: (member_expression object: (identifier) @function))
+          ]
+          arguments: (arguments . [(string) (template_string)] @name)
+          (#match? @function "^(it|test)$")) @test
+    "#;
+
+    #[derive(PartialEq)]
+    enum Kind {
+        Test,
+        Namespace,
+    }
+
+    struct Definition {
+        kind: Kind,
+        name: String,
+        // The names of the test patterns matching the definition, like the regex-based search
+        pattern_names: Vec<String>,
+        start_row: usize,
+        end_row: usize,
+        start_byte: usize,
+    }
+
+    impl Definition {
+        fn encloses(&self, row: usize) -> bool {
+            self.start_row <= row && row <= self.end_row
+        }
+    }
+
+    pub struct Grammar {
+        language: Language,
+        query: &'static str,
+        // Rust tests are functions with an attribute matching the test pattern, the rest of the
+        // tests have the line of their name matching it, like in the regex-based search
+        test_attributes: bool,
+    }
+
+    impl Grammar {
+        #[cfg_attr(not(feature = "tree-sitter-javascript"), allow(unused_variables))]
+        pub fn find(context: &Context, language: &str) -> Option<Self> {
+            match language {
+                #[cfg(feature = "tree-sitter-rust")]
+                "rust" => Some(Self {
+                    language: tree_sitter_rust::LANGUAGE.into(),
+                    query: RUST_QUERY,
+                    test_attributes: true,
+                }),
+                #[cfg(feature = "tree-sitter-python")]
+                "python" => Some(Self {
+                    language: tree_sitter_python::LANGUAGE.into(),
+                    query: PYTHON_QUERY,
+                    test_attributes: false,
+                }),
+                // TypeScript can't be parsed with the JavaScript grammar
+                #[cfg(feature = "tree-sitter-javascript")]
+                "javascript"
+                    if context
+                        .path()
+                        .extension()
+                        .and_then(|ext| ext.to_str())
+                        .is_some_and(|ext| ["js", "jsx", "mjs", "cjs"].contains(&ext)) =>
+                {
+                    Some(Self {
+                        language: tree_sitter_javascript::LANGUAGE.into(),
+                        query: JAVASCRIPT_QUERY,
+                        test_attributes: false,
+                    })
+                }
+                _ => None,
+            }
+        }
+
+        /// Finds the innermost test enclosing the line along with all the enclosing namespaces.
+        pub fn find_nearest(
+            &self,
+            context: &Context,
+            test_patterns: &[NamedPattern],
+        ) -> Result<Nearest, Error> {
+            let row = context
+                .line_nr()
+                .ok_or_else(|| Error::LineRequired(context.path().clone()))?
+                - 1;
+            let source = fs::read_to_string(context.path())?;
+            let mut parser = Parser::new();
+            parser
+                .set_language(&self.language)
+                .map_err(|error| Error::Other(error.to_string()))?;
+            let tree = parser
+                .parse(&source, None)
+                .ok_or_else(|| Error::Parse(context.path().clone()))?;
+            let query = Query::new(&self.language, self.query)
+                .map_err(|error| Error::Other(error.to_string()))?;
+            let mut definitions = vec![];
+            let mut cursor = QueryCursor::new();
+            let mut matches = cursor.matches(&query, tree.root_node(), source.as_bytes());
+
+            while let Some(query_match) = matches.next() {
+                let mut name = None;
+                let mut definition = None;
+
+                for capture in query_match.captures {
+                    match query.capture_names()[capture.index as usize] {
+                        "name" => {
+                            name = Some((
+                                capture
+                                    .node
+                                    .utf8_text(source.as_bytes())
+                                    .map_err(|_| Error::Parse(context.path().clone()))?,
+                                capture.node.start_position().row,
+                            ))
+                        }
+                        "test" => definition = Some((Kind::Test, capture.node)),
+                        "namespace" => definition = Some((Kind::Namespace, capture.node)),
+                        _ => {}
+                    }
+                }
+
+                let (Some((name, name_row)), Some((kind, node))) = (name, definition) else {
+                    continue;
+                };
+                let mut start_row = node.start_position().row;
+                let mut pattern_names = vec![];
+
+                if kind == Kind::Test && self.test_attributes {
+                    let attributes = attributes(node);
+                    let matches = attributes
+                        .iter()
+                        .filter_map(|attribute| {
+                            let text = attribute.utf8_text(source.as_bytes()).unwrap_or_default();
+
+                            test_patterns.iter().find_map(|pattern| pattern.find(text))
+                        })
+                        .collect::<Vec<_>>();
+
+                    if matches.is_empty() {
+                        continue;
+                    }
+                    pattern_names = matches
+                        .into_iter()
+                        .filter_map(|(_, name)| name.cloned())
+                        .collect();
+
+                    // The test starts at its first attribute, like the regex-based search does
+                    start_row = attributes
+                        .last()
+                        .map_or(start_row, |attribute| attribute.start_position().row);
+                } else if kind == Kind::Test {
+                    // The line of the name, e.g. `])("name", ...)` closing a multi-line `it.each`
+                    let line = source.lines().nth(name_row).unwrap_or_default();
+                    let Some((_, name)) =
+                        test_patterns.iter().find_map(|pattern| pattern.find(line))
+                    else {
+                        continue;
+                    };
+
+                    pattern_names = name.cloned().into_iter().collect();
+                }
+
+                definitions.push(Definition {
+                    kind,
+                    name: unquote(name),
+                    pattern_names,
+                    start_row,
+                    end_row: node.end_position().row,
+                    start_byte: node.start_byte(),
+                });
+            }
+
+            definitions.sort_by_key(|definition| definition.start_byte);
+
+            let namespaces = definitions
+                .iter()
+                .filter(|definition| definition.kind == Kind::Namespace && definition.encloses(row))
+                .collect::<Vec<_>>();
+            // Outside of a test, the nearest one is the last test above the line in the same namespace
+            let test = definitions
+                .iter()
+                .rfind(|definition| definition.kind == Kind::Test && definition.encloses(row))
+                .or_else(|| {
+                    definitions.iter().rfind(|definition| {
+                        definition.kind == Kind::Test
+                            && definition.end_row < row
+                            && definitions
+                                .iter()
+                                .filter(|namespace| {
+                                    namespace.kind == Kind::Namespace
+                                        && namespace.encloses(definition.start_row)
+                                })
+                                .map(|namespace| namespace.start_byte)
+                                .eq(namespaces.iter().map(|namespace| namespace.start_byte))
+                    })
+                });
+            let namespaces = namespaces
+                .into_iter()
+                .map(|definition| definition.name.clone())
+                .collect();
+
+            Ok(Nearest::new(
+                test.map(|test| test.name.clone()).into_iter().collect(),
+                namespaces,
+                test.map(|test| test.start_row + 1),
+                test.map(|test| test.pattern_names.clone())
+                    .unwrap_or_default(),
+            ))
+        }
+    }
+
+    /// The attributes preceding the item, the closest first.
+    fn attributes(node: Node) -> Vec<Node> {
+        let mut attributes = vec![];
+        let mut sibling = node.prev_sibling();
+
+        while let Some(node) = sibling {
+            match node.kind() {
+                "attribute_item" => attributes.push(node),
+                "line_comment" | "block_comment" => {}
+                _ => break,
+            }
+            sibling = node.prev_sibling();
+        }
+
+        attributes
+    }
+
+    fn unquote(name: &str) -> String {
+        ['"', '\'', '`']
+            .into_iter()
+            .find_map(|quote| {
+                name.strip_prefix(quote)
+                    .and_then(|name| name.strip_suffix(quote))
+            })
+            .unwrap_or(name)
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(path: &str, line_nr: usize, language: &str) -> Option<(Vec<String>, Vec<String>)> {
+        let test_pattern = match language {
+            "python" => r"\s*(?:async )?def (test_\w+)",
+            "javascript" => r#"^\s*(?:it|test)\s*[\( ]\s*["'\`](.*?)["'\`]"#,
+            _ => r"(#\[(?:\w+::|rs)?test)",
+        };
+
+        find_with(path, line_nr, language, test_pattern)
+    }
+
+    fn find_with(
+        path: &str,
+        line_nr: usize,
+        language: &str,
+        test_pattern: &str,
+    ) -> Option<(Vec<String>, Vec<String>)> {
+        let context = Context::new(
+            Some("tests/fixtures/tree-sitter"),
+            path,
+            Some(line_nr),
+            None,
+            None,
+        )
+        .unwrap();
+
+        find_nearest(&context, language, &[test_pattern.into()])
+            .unwrap()
+            .map(|nearest| (nearest.namespaces().to_vec(), nearest.tests().to_vec()))
+    }
+
+    #[test]
+    fn test_find_nearest_without_grammar() {
+        assert!(find("test_nested.py", 1, "ruby").is_none());
+    }
+
+    #[cfg(feature = "tree-sitter-rust")]
+    #[test]
+    fn test_find_nearest_rust() {
+        assert_eq!(
+            find("src/lib.rs", 8, "rust"),
+            Some((
+                vec!["tests".into(), "nested".into()],
+                vec!["it_works".into()]
+            ))
+        );
+        assert_eq!(
+            find("src/lib.rs", 14, "rust"),
+            Some((vec!["tests".into()], vec!["it_panics".into()]))
+        );
+        assert_eq!(
+            find("src/lib.rs", 12, "rust"),
+            Some((vec!["tests".into()], vec![]))
+        );
+    }
+
+    #[cfg(feature = "tree-sitter-rust")]
+    #[test]
+    fn test_find_nearest_pattern_names() {
+        let context = Context::new(
+            Some("tests/fixtures/tree-sitter"),
+            "src/lib.rs",
+            Some(14),
+            None,
+            None,
+        )
+        .unwrap();
+        let test_patterns = [
+            NamedPattern::new(r"(#\[should_panic)", Some("panic")),
+            NamedPattern::new(r"(#\[test)", None),
+        ];
+        let nearest = find_nearest(&context, "rust", &test_patterns)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(nearest.names(), ["panic".to_string()]);
+    }
+
+    #[cfg(feature = "tree-sitter-python")]
+    #[test]
+    fn test_find_nearest_python() {
+        assert_eq!(
+            find("test_nested.py", 5, "python"),
+            Some((
+                vec!["TestOuter".into(), "TestInner".into()],
+                vec!["test_multiline".into()]
+            ))
+        );
+        assert_eq!(
+            find("test_nested.py", 10, "python"),
+            Some((vec!["TestOuter".into()], vec!["test_outer".into()]))
+        );
+        assert_eq!(
+            find("test_nested.py", 13, "python"),
+            Some((vec!["TestOuter".into()], vec!["test_outer".into()]))
+        );
+    }
+
+    #[cfg(feature = "tree-sitter-python")]
+    #[test]
+    fn test_find_nearest_configured_pattern() {
+        assert_eq!(
+            find_with("test_nested.py", 13, "python", r"\s*def (check_\w+)"),
+            Some((vec!["TestOuter".into()], vec!["check_sum".into()]))
+        );
+    }
+
+    #[cfg(feature = "tree-sitter-javascript")]
+    #[test]
+    fn test_find_nearest_javascript() {
+        assert_eq!(
+            find("math.test.js", 3, "javascript"),
+            Some((vec!["Math %i".into()], vec!["adds".into()]))
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use crate::LineNr;
+use std::process::Command;
+
+/// A command that runs the tests along with the details of how it was resolved.
+#[derive(Debug)]
+pub struct TestCommand {
+    command: Command,
+    framework: String,
+    language: String,
+    test_name: Option<String>,
+    test_line_nr: Option<LineNr>,
+    namespaces: Vec<String>,
+}
+
+impl TestCommand {
+    pub fn new(command: Command, framework: &str, language: &str) -> Self {
+        Self {
+            command,
+            framework: framework.to_string(),
+            language: language.to_string(),
+            test_name: None,
+            test_line_nr: None,
+            namespaces: vec![],
+        }
+    }
+
+    pub fn with_nearest(
+        self,
+        test_name: Option<String>,
+        test_line_nr: Option<LineNr>,
+        namespaces: Vec<String>,
+    ) -> Self {
+        Self {
+            test_name,
+            test_line_nr,
+            namespaces,
+            ..self
+        }
+    }
+
+    pub fn command(&self) -> &Command {
+        &self.command
+    }
+
+    pub fn into_command(self) -> Command {
+        self.command
+    }
+
+    /// The test framework identifier, e.g. `rspec`.
+    pub fn framework(&self) -> &str {
+        &self.framework
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// The name of the nearest test, only resolved for the line scope.
+    pub fn test_name(&self) -> Option<&str> {
+        self.test_name.as_deref()
+    }
+
+    /// The line of the nearest test, only resolved for the line scope.
+    pub fn test_line_nr(&self) -> Option<LineNr> {
+        self.test_line_nr
+    }
+
+    /// The namespaces of the nearest test, only resolved for the line scope.
+    pub fn namespaces(&self) -> &[String] {
+        &self.namespaces
+    }
+}
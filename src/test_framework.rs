@@ -1,8 +1,8 @@
 use crate::{
-    context::Nearest, language::Language, named_pattern::NamedPattern, ArgsList, Context, Scope,
+    alternate::Projection, config::FrameworkConfig, context::Nearest, language::Language,
+    named_pattern::NamedPattern, syntax, ArgsList, Context, Error, Scope,
 };
 use regex::Regex;
-use std::error::Error;
 
 pub trait TestFrameworkMeta {
     fn language(&self) -> &dyn Language;
@@ -22,11 +22,22 @@ pub trait TestFrameworkMeta {
     fn test_pattern(&self) -> &str;
 
     fn namespace_pattern(&self) -> &str;
+
+    /// The projections between the source files and the test files, see [`Projection`].
+    fn projections(&self) -> &[Projection];
+
+    fn configure(&mut self, config: &FrameworkConfig);
 }
 
 pub trait TestFramework: TestFrameworkMeta {
     fn test_patterns(&self) -> Vec<NamedPattern> {
-        vec![self.test_pattern().into()]
+        let test_pattern = self.test_pattern();
+
+        if test_pattern.is_empty() {
+            vec![]
+        } else {
+            vec![test_pattern.into()]
+        }
     }
 
     fn namespace_patterns(&self) -> Vec<NamedPattern> {
@@ -39,9 +50,11 @@ pub trait TestFramework: TestFrameworkMeta {
         }
     }
 
-    fn is_suitable_for(&self, context: &Context) -> bool {
+    /// Matches the pattern against the path relative to the root, so the directories the
+    /// project is checked out in (e.g. `~/test/app`) don't count.
+    fn matches_pattern(&self, context: &Context) -> bool {
         match self.pattern() {
-            Ok(pattern) => pattern.is_match(context.path().to_str().unwrap_or_default()),
+            Ok(pattern) => pattern.is_match(context.rel_str()),
             Err(error) => {
                 log::warn!("{}", error);
                 false
@@ -49,40 +62,107 @@ pub trait TestFramework: TestFrameworkMeta {
         }
     }
 
+    fn is_suitable_for(&self, context: &Context) -> bool {
+        self.matches_pattern(context)
+    }
+
+    /// The files marking the root of a project (e.g. `package.json`). The tests run from the
+    /// nearest directory containing one of them, see [`Context::within_project`].
+    fn root_markers(&self) -> &[&str] {
+        &[]
+    }
+
     fn build_executable(&self, _context: &Context) -> ArgsList {
         vec![]
     }
 
+    /// Whether a single case of a parameterized test can be picked (`--case`).
+    fn supports_cases(&self) -> bool {
+        false
+    }
+
     fn executable(&self, context: &Context) -> ArgsList {
         self.default_executable()
             .unwrap_or(self.build_executable(context))
     }
 
-    fn build_suite_position_args(&self, _context: &Context) -> Result<ArgsList, Box<dyn Error>> {
+    fn build_suite_position_args(&self, _context: &Context) -> Result<ArgsList, Error> {
         Ok(vec![])
     }
 
-    fn build_file_position_args(&self, context: &Context) -> Result<ArgsList, Box<dyn Error>> {
+    fn build_file_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
         Ok(vec![context.rel_str().into()])
     }
 
-    fn build_line_position_args(&self, context: &Context) -> Result<ArgsList, Box<dyn Error>> {
+    fn build_line_position_args(&self, context: &Context) -> Result<ArgsList, Error> {
         Ok(vec![context.rel_full()])
     }
 
-    fn position_args(&self, context: &Context) -> Result<ArgsList, Box<dyn Error>> {
+    fn position_args(&self, context: &Context) -> Result<ArgsList, Error> {
         match context.scope() {
             Scope::Suite => self.build_suite_position_args(context),
             Scope::File => self.build_file_position_args(context),
+            Scope::Line if context.case().is_some() && !self.supports_cases() => {
+                Err(Error::CaseNotSupported {
+                    framework: self.name().into(),
+                })
+            }
             Scope::Line => self.build_line_position_args(context),
         }
     }
 
-    fn find_nearest(&self, context: &Context) -> Result<Nearest, Box<dyn Error>> {
+    /// Merges the position arguments of several contexts into as few invocations as possible,
+    /// by default every context is run separately.
+    fn merge_position_args(&self, contexts: &[&Context]) -> Result<Vec<ArgsList>, Error> {
+        contexts
+            .iter()
+            .map(|context| self.position_args(context))
+            .collect()
+    }
+
+    fn nearest_test_name(
+        &self,
+        _context: &Context,
+        nearest: &Nearest,
+    ) -> Result<Option<String>, Error> {
+        Ok(nearest.tests().first().cloned())
+    }
+
+    fn find_nearest(&self, context: &Context) -> Result<Nearest, Error> {
         if let Some(line) = context.line_nr() {
+            if let Some(nearest) =
+                syntax::find_nearest(context, self.language_name(), &self.test_patterns())?
+            {
+                return Ok(nearest);
+            }
+
             context.find_nearest(&self.test_patterns(), &self.namespace_patterns(), line..=1)
         } else {
-            Err("Line number is required".into())
+            Err(Error::LineRequired(context.path().clone()))
         }
     }
 }
+
+/// Passes the position arguments of all the contexts to a single invocation, for the test
+/// frameworks accepting several locations (e.g. `rspec a_spec.rb:1 b_spec.rb:2`).
+pub fn concat_position_args<T: TestFramework + ?Sized>(
+    test_framework: &T,
+    contexts: &[&Context],
+) -> Result<Vec<ArgsList>, Error> {
+    let mut all_args: Vec<ArgsList> = vec![];
+
+    for context in contexts {
+        let position_args = test_framework.position_args(context)?;
+
+        // The whole suite covers the rest of the locations
+        if matches!(context.scope(), Scope::Suite) || position_args.is_empty() {
+            return Ok(vec![position_args]);
+        }
+
+        if !all_args.contains(&position_args) {
+            all_args.push(position_args);
+        }
+    }
+
+    Ok(vec![all_args.concat()])
+}
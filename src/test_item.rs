@@ -0,0 +1,45 @@
+use crate::LineNr;
+use std::process::Command;
+
+/// A test found in a file along with the command that runs it.
+#[derive(Debug)]
+pub struct TestItem {
+    line_nr: LineNr,
+    name: String,
+    namespaces: Vec<String>,
+    command: Command,
+}
+
+impl TestItem {
+    pub fn new(line_nr: LineNr, name: String, namespaces: Vec<String>, command: Command) -> Self {
+        Self {
+            line_nr,
+            name,
+            namespaces,
+            command,
+        }
+    }
+
+    pub fn line_nr(&self) -> LineNr {
+        self.line_nr
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn namespaces(&self) -> &[String] {
+        &self.namespaces
+    }
+
+    /// The namespaces and the test name, e.g. `Math Addition adds two numbers`.
+    pub fn full_name(&self) -> String {
+        let mut names = self.namespaces.clone();
+        names.push(self.name.clone());
+        names.join(" ")
+    }
+
+    pub fn command(&self) -> &Command {
+        &self.command
+    }
+}
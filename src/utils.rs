@@ -21,6 +21,43 @@ pub fn is_executable(binary_name: &str) -> bool {
     which::which(binary_name).is_ok()
 }
 
-pub fn quote(in_str: &str) -> String {
-    shlex::try_quote(in_str).unwrap_or_default().to_string()
+/// The comma-separated items following an opening bracket, up to the closing one, e.g. the
+/// arguments of a call or the elements of a list literal.
+pub fn items(text: &str) -> Vec<&str> {
+    let mut items = vec![];
+    let mut depth = 0;
+    let mut quote = None;
+    let mut start = 0;
+
+    for (index, c) in text.char_indices() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'' | '`') => quote = Some(c),
+            (None, '(' | '[' | '{') => depth += 1,
+            (None, ')' | ']' | '}') if depth == 0 => {
+                items.push(&text[start..index]);
+                break;
+            }
+            (None, ')' | ']' | '}') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                items.push(&text[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    items
+        .into_iter()
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// The content of a string literal.
+pub fn unquote(text: &str) -> Option<&str> {
+    ['"', '\'', '`']
+        .into_iter()
+        .find_map(|quote| text.strip_prefix(quote)?.strip_suffix(quote))
 }
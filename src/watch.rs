@@ -0,0 +1,179 @@
+use anytest::{Context, Shell};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::{
+    error::Error,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::Duration,
+};
+
+const DEBOUNCE: Duration = Duration::from_millis(200);
+const CLEAR_SCREEN: &str = "\x1B[2J\x1B[1;1H";
+
+struct Filter {
+    root: PathBuf,
+}
+
+impl Filter {
+    fn new(root: &Path) -> Self {
+        let root = fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+
+        Self { root }
+    }
+
+    fn is_relevant(&self, path: &Path) -> bool {
+        let Ok(rel) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+
+        if rel.starts_with(".git") {
+            return false;
+        }
+
+        !self.is_ignored(path)
+    }
+
+    /// Follows git: the nearest `.gitignore` that matches the path wins, and `.git/info/exclude`
+    /// applies when none of them does. The files are read on every check, so the changes to
+    /// them are picked up right away.
+    fn is_ignored(&self, path: &Path) -> bool {
+        let is_dir = path.is_dir();
+        let dirs = path
+            .parent()
+            .into_iter()
+            .flat_map(Path::ancestors)
+            .take_while(|dir| dir.starts_with(&self.root));
+
+        for dir in dirs {
+            let gitignore = gitignore(dir, &dir.join(".gitignore"));
+            let matched = gitignore.matched_path_or_any_parents(path, is_dir);
+
+            if !matched.is_none() {
+                return matched.is_ignore();
+            }
+        }
+
+        gitignore(&self.root, &self.root.join(".git/info/exclude"))
+            .matched_path_or_any_parents(path, is_dir)
+            .is_ignore()
+    }
+
+    fn is_relevant_event(&self, event: &Event) -> bool {
+        matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+        ) && event.paths.iter().any(|path| self.is_relevant(path))
+    }
+}
+
+fn gitignore(dir: &Path, file: &Path) -> Gitignore {
+    if !file.is_file() {
+        return Gitignore::empty();
+    }
+
+    let mut builder = GitignoreBuilder::new(dir);
+
+    if let Some(error) = builder.add(file) {
+        log::debug!("{}", error);
+    }
+
+    builder.build().unwrap_or_else(|error| {
+        log::warn!("{}", error);
+        Gitignore::empty()
+    })
+}
+
+/// Runs the test and runs it again every time a file in the root directory changes.
+/// The command is rebuilt before every run so the nearest test is resolved against
+/// the current file contents.
+pub fn run(context: &Context, shell: Shell) -> Result<(), Box<dyn Error>> {
+    let filter = Filter::new(context.root());
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+
+    watcher.watch(context.root(), RecursiveMode::Recursive)?;
+
+    loop {
+        print!("{}", CLEAR_SCREEN);
+        io::stdout().flush()?;
+
+        match anytest::build_command(context) {
+            Ok(mut command) => {
+                println!("{}", shell.format(&command));
+
+                if let Err(error) = anytest::run_command(&mut command) {
+                    eprintln!("Error: {}", error);
+                }
+            }
+            Err(error) => eprintln!("Error: {}", error),
+        }
+
+        // The changes made while the test was running are kept, so a save during the run
+        // reruns the test right after it
+        wait_for_changes(&rx, &filter)?;
+    }
+}
+
+fn wait_for_changes(
+    rx: &mpsc::Receiver<notify::Result<Event>>,
+    filter: &Filter,
+) -> Result<(), Box<dyn Error>> {
+    loop {
+        match rx.recv()? {
+            Ok(event) if filter.is_relevant_event(&event) => break,
+            Ok(_) => {}
+            Err(error) => log::warn!("{}", error),
+        }
+    }
+
+    // Let the burst settle before the run, e.g. editors often write a file several times on save
+    while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+        if let Err(error) = event {
+            log::warn!("{}", error);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_filter_is_relevant() {
+        let root = fs::canonicalize(env::current_dir().unwrap()).unwrap();
+        let filter = Filter::new(&root);
+
+        assert!(filter.is_relevant(&root.join("src/lib.rs")));
+        assert!(filter.is_relevant(&root.join("tests/fixtures/rspec/normal_spec.rb")));
+        assert!(!filter.is_relevant(&root.join("target/debug/anytest")));
+        assert!(!filter.is_relevant(&root.join(".git/HEAD")));
+        assert!(!filter.is_relevant(Path::new("/outside/of/root.rs")));
+    }
+
+    #[test]
+    fn test_filter_nested_gitignore() {
+        let root = env::temp_dir().join("anytest-test-watch");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join(".git/info")).unwrap();
+        fs::create_dir_all(root.join("web/dist")).unwrap();
+        fs::write(root.join(".gitignore"), "*.log\n").unwrap();
+        fs::write(root.join(".git/info/exclude"), "scratch.rs\n").unwrap();
+        fs::write(root.join("web/.gitignore"), "dist/\n!keep.log\n").unwrap();
+
+        let root = fs::canonicalize(root).unwrap();
+        let filter = Filter::new(&root);
+
+        assert!(filter.is_relevant(&root.join("web/src/app.js")));
+        assert!(!filter.is_relevant(&root.join("web/dist/app.js")));
+        assert!(!filter.is_relevant(&root.join("web/debug.log")));
+        assert!(filter.is_relevant(&root.join("web/keep.log")));
+        assert!(!filter.is_relevant(&root.join("scratch.rs")));
+        assert!(!filter.is_relevant(&root.join("debug.log")));
+    }
+}
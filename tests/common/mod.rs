@@ -16,8 +16,25 @@ impl Project {
     }
 
     fn test(&self, file: &str, line: Option<LineNr>, scope: Scope) -> String {
-        let context =
-            Context::new(Some(self.root.to_str().unwrap()), file, line, Some(scope)).unwrap();
+        self.test_with_case(file, line, scope, None)
+    }
+
+    fn test_with_case(
+        &self,
+        file: &str,
+        line: Option<LineNr>,
+        scope: Scope,
+        case: Option<&str>,
+    ) -> String {
+        let context = Context::new(
+            Some(self.root.to_str().unwrap()),
+            file,
+            line,
+            Some(scope),
+            None,
+        )
+        .unwrap()
+        .with_case(case);
         let command = anytest::build_command(&context).unwrap();
 
         anytest::format_command(&command)
@@ -27,6 +44,12 @@ impl Project {
         self.test(file, Some(line), Scope::Line)
     }
 
+    // Only the frameworks supporting parameterized tests use it
+    #[allow(dead_code)]
+    pub fn test_case(&self, file: &str, line: anytest::LineNr, case: &str) -> String {
+        self.test_with_case(file, Some(line), Scope::Line, Some(case))
+    }
+
     pub fn test_file(&self, file: &str) -> String {
         self.test(file, None, Scope::File)
     }
@@ -34,4 +57,34 @@ impl Project {
     pub fn test_suite(&self, file: &str) -> String {
         self.test(file, None, Scope::Suite)
     }
+
+    // Only the frameworks with projections use it
+    #[allow(dead_code)]
+    pub fn alternate(&self, file: &str) -> String {
+        let context =
+            Context::new(Some(self.root.to_str().unwrap()), file, None, None, None).unwrap();
+
+        anytest::find_alternate(&context)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    pub fn list(&self, file: &str) -> Vec<(LineNr, String, String)> {
+        let context =
+            Context::new(Some(self.root.to_str().unwrap()), file, None, None, None).unwrap();
+
+        anytest::list_tests(&context)
+            .unwrap()
+            .iter()
+            .map(|test| {
+                (
+                    test.line_nr(),
+                    test.full_name(),
+                    anytest::format_command(test.command()),
+                )
+            })
+            .collect()
+    }
 }
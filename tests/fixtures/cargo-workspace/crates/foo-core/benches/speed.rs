@@ -0,0 +1,3 @@
+#[test]
+fn it_is_fast() {
+}
@@ -0,0 +1,5 @@
+fn main() {}
+
+#[test]
+fn it_demos() {
+}
@@ -0,0 +1,8 @@
+fn main() {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_runs() {
+    }
+}
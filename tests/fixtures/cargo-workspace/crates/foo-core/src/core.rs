@@ -0,0 +1,17 @@
+mod parser;
+
+#[path = "utils/helpers.rs"]
+mod util;
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_works() {
+    }
+
+    mod parsing {
+        #[test]
+        fn it_parses_nested() {
+        }
+    }
+}
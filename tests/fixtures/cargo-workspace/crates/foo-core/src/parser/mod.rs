@@ -0,0 +1,12 @@
+mod lexer;
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_parses() {
+    }
+}
+
+#[cfg(test)]
+#[path = "parser_tests.rs"]
+mod external_tests;
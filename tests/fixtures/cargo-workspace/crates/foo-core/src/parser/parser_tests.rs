@@ -0,0 +1,3 @@
+#[test]
+fn it_parses_externally() {
+}
@@ -0,0 +1,6 @@
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_helps() {
+    }
+}
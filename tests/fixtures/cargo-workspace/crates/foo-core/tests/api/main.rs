@@ -0,0 +1,5 @@
+mod users;
+
+#[test]
+fn it_serves() {
+}
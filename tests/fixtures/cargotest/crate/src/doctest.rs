@@ -0,0 +1,42 @@
+//! Module docs
+//!
+//! ```
+//! assert!(true);
+//! ```
+
+/// Adds two numbers.
+///
+/// ```
+/// assert_eq!(crate::add(1, 2), 3);
+/// ```
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+pub struct Calculator;
+
+impl Calculator {
+    /// Multiplies two numbers.
+    ///
+    /// ```
+    /// let _ = 1;
+    /// ```
+    #[inline]
+    pub fn mul(&self, a: i32, b: i32) -> i32 {
+        a * b
+    }
+}
+
+pub mod inner {
+    /// ```
+    /// let _ = 2;
+    /// ```
+    pub const ANSWER: u8 = 42;
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_adds() {
+    }
+}
@@ -25,4 +25,15 @@ mod tests {
     #[actix_rt::test]
     async fn test_actix_rt() {
     }
+
+    #[rstest]
+    #[case(1)]
+    #[case::two(2)]
+    fn rstest_case_test(#[case] _input: u8) {
+    }
+
+    #[test_case(1 ; "one")]
+    #[test_case(2)]
+    fn test_case_test(_input: u8) {
+    }
 }
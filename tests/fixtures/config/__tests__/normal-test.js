@@ -0,0 +1,8 @@
+// comment
+describe('Math', function () {
+  describe(`Addition`, function () {
+    it('adds two numbers', function () {
+      // assertions
+    });
+  });
+});
@@ -0,0 +1,2 @@
+RSpec.describe Calc do
+end
@@ -0,0 +1,2 @@
+def latest_question(questions):
+    return max(questions, key=lambda question: question.pub_date)
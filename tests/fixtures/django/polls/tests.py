@@ -0,0 +1,9 @@
+from django.test import TestCase
+
+
+class QuestionModelTests(TestCase):
+    def test_was_published_recently(self):
+        self.assertIs(False, False)
+
+    def test_str(self):
+        self.assertEqual("question", "question")
@@ -0,0 +1,7 @@
+defmodule CalcTest do
+  use ExUnit.Case
+
+  test "adds two numbers" do
+    assert Calc.add(1, 1) == 2
+  end
+end
@@ -0,0 +1,9 @@
+ExUnit.start
+
+defmodule OtherTest do
+  use ExUnit.Case
+
+  test "other" do
+    assert true
+  end
+end
@@ -0,0 +1,33 @@
+package calc
+
+import "testing"
+
+func TestAdd(t *testing.T) {
+	t.Run("positive numbers", func(t *testing.T) {
+		if Add(1, 2) != 3 {
+			t.Fail()
+		}
+	})
+
+	t.Run("negative", func(t *testing.T) {
+		t.Run("a+b", func(t *testing.T) {
+			if Add(-1, -2) != -3 {
+				t.Fail()
+			}
+		})
+	})
+
+	if Add(0, 0) != 0 {
+		t.Fail()
+	}
+}
+
+func helper(t *testing.T) {
+	t.Run("not a test", func(t *testing.T) {})
+}
+
+func BenchmarkAdd(b *testing.B) {
+	for i := 0; i < b.N; i++ {
+		Add(1, 2)
+	}
+}
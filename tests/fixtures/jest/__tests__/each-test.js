@@ -0,0 +1,12 @@
+describe.each([1, 2])('Math with %i', (a) => {
+  it.each([[1, 1, 2], [1, 2, 3]])('add(%i, %i) -> %i', (b, c, expected) => {
+    // assertions
+  });
+
+  test.each([
+    [1, 1],
+    [2, 4],
+  ])('%# squares $n', (n, expected) => {
+    // assertions
+  });
+});
@@ -0,0 +1,5 @@
+import { add } from './math';
+
+test('adds two numbers', () => {
+  expect(add(1, 1)).toBe(2);
+});
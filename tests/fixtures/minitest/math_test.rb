@@ -0,0 +1,11 @@
+require "minitest/autorun"
+
+class MathTest < Minitest::Test
+  def test_addition
+    assert_equal 2, 1 + 1
+  end
+
+  test "adds two numbers" do
+    assert_equal 2, 1 + 1
+  end
+end
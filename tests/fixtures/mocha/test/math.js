@@ -0,0 +1,9 @@
+const assert = require('assert');
+
+describe('Math', function () {
+  describe('Addition', function () {
+    it('adds two numbers (1 + 1)', function () {
+      assert.equal(1 + 1, 2);
+    });
+  });
+});
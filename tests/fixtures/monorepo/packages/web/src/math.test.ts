@@ -0,0 +1,8 @@
+import { describe, expect, it } from 'vitest'
+import { add } from './math'
+
+describe('add', () => {
+  it('adds two numbers', () => {
+    expect(add(1, 1)).toBe(2)
+  })
+})
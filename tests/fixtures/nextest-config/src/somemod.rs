@@ -0,0 +1,13 @@
+mod tests {
+    #[test]
+    fn first_test () {
+    }
+
+    #[test]
+    fn second_test () {
+    }
+
+    #[test]
+    fn third_test () {
+    }
+}
@@ -0,0 +1,8 @@
+from unittest.mock import patch
+
+
+def test_foo():
+    with patch("os.getcwd", return_value="/tmp"):
+        import os
+
+        assert os.getcwd() == "/tmp"
@@ -0,0 +1,2 @@
+def charge(amount):
+    return amount
@@ -0,0 +1,6 @@
+import pytest
+
+
+@pytest.mark.parametrize("a,b,expected", [(1, 1, 2), (1, 2, 3)], ids=["ones", "twos"])
+def test_add(a, b, expected):
+    assert a + b == expected
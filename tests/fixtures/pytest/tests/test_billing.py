@@ -0,0 +1,5 @@
+from pkg.billing import charge
+
+
+def test_charge():
+    assert charge(1) == 1
@@ -0,0 +1,14 @@
+import unittest
+
+
+class TestNumbers(unittest.TestCase):
+    def test_addition(self):
+        self.assertEqual(1 + 1, 2)
+
+    class TestNested(unittest.TestCase):
+        def test_nested(self):
+            self.assertEqual(1, 1)
+
+
+if __name__ == "__main__":
+    unittest.main()
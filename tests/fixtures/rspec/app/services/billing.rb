@@ -0,0 +1,5 @@
+class Billing
+  def charge(amount)
+    amount
+  end
+end
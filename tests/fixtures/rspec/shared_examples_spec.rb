@@ -0,0 +1,15 @@
+RSpec.shared_examples "a collection" do
+  it "is empty" do
+    expect(subject).to be_empty
+  end
+end
+
+RSpec.describe Array do
+  it_behaves_like "a collection"
+
+  [1, 2].each do |n|
+    it "includes #{n}" do
+      expect([1, 2]).to include(n)
+    end
+  end
+end
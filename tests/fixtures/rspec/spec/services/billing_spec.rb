@@ -0,0 +1,5 @@
+RSpec.describe Billing do
+  it "charges the amount" do
+    expect(Billing.new.charge(1)).to eq 1
+  end
+end
@@ -0,0 +1,8 @@
+const assert = require('assert')
+const { add } = require('../src/math')
+
+describe('add', () => {
+  it('adds two numbers', () => {
+    assert.equal(add(1, 1), 2)
+  })
+})
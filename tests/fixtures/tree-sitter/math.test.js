@@ -0,0 +1,5 @@
+describe.each([1, 2])('Math %i', (n) => {
+  it('adds', () => {
+    expect(n + n).toBe(2 * n);
+  });
+});
@@ -0,0 +1,18 @@
+#[cfg(test)]
+mod tests {
+    mod nested {
+        #[test]
+        fn it_works() {
+            assert_eq!(
+                2 + 2,
+                4
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_panics() {
+        panic!();
+    }
+}
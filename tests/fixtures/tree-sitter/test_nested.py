@@ -0,0 +1,13 @@
+class TestOuter:
+    class TestInner:
+        def test_multiline(
+            self,
+            value=1,
+        ):
+            assert value == 1
+
+    def test_outer(self):
+        assert True
+
+    def check_sum(self):
+        assert 1 + 1 == 2
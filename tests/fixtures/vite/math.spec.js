@@ -0,0 +1,5 @@
+describe('Math', () => {
+  it('adds two numbers', () => {
+    expect(1 + 1).toBe(2)
+  })
+})
@@ -0,0 +1,13 @@
+import { describe, expect, it } from 'vitest'
+
+describe('Math', () => {
+  describe('Addition (sum)', () => {
+    it('adds two numbers', () => {
+      expect(1 + 1).toBe(2)
+    })
+  })
+})
+
+test('standalone', () => {
+  expect(true).toBe(true)
+})
@@ -1,4 +1,5 @@
-use anytest::{Context, Scope};
+use anytest::{Context, Error, LineNr, Scope};
+use std::{env, fs, path::Path, process};
 
 #[test]
 fn test_build_command() {
@@ -7,6 +8,7 @@ fn test_build_command() {
         "tests/integration_test.rs",
         Some(3),
         Some(Scope::Line),
+        None,
     )
     .unwrap();
     let command = anytest::build_command(&context).unwrap();
@@ -27,3 +29,439 @@ fn test_build_command() {
         ]
     );
 }
+
+#[test]
+fn test_build_command_with_framework() {
+    let context = Context::new(
+        Some("tests/fixtures/espec"),
+        "normal_spec.exs",
+        None,
+        Some(Scope::File),
+        Some("exunit"),
+    )
+    .unwrap();
+    let command = anytest::build_command(&context).unwrap();
+
+    assert_eq!(anytest::format_command(&command), "elixir normal_spec.exs");
+}
+
+#[test]
+fn test_build_command_with_unknown_framework() {
+    let context = Context::new(
+        Some("tests/fixtures/espec"),
+        "normal_spec.exs",
+        None,
+        Some(Scope::File),
+        Some("unknown"),
+    )
+    .unwrap();
+    let error = anytest::build_command(&context).unwrap_err();
+
+    let message = error.to_string();
+
+    assert!(message.starts_with("Unknown test framework `unknown`, available test frameworks: "));
+    assert!(message.contains("exunit"));
+    assert!(message.contains("jest"));
+    assert!(matches!(
+        error,
+        Error::UnknownFramework { name, available }
+            if name == "unknown" && available.iter().any(|name| name == "jest")
+    ));
+}
+
+#[test]
+fn test_build_command_without_framework() {
+    let context =
+        Context::new(Some("tests/fixtures/folder"), "file.txt", None, None, None).unwrap();
+    let error = anytest::build_command(&context).unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::NoFramework { path, candidates }
+            if path.ends_with("folder/file.txt") && candidates.is_empty()
+    ));
+}
+
+#[test]
+fn test_build_command_without_test_at_line() {
+    let context = Context::new(
+        Some("tests/fixtures/pytest"),
+        "test_parametrize.py",
+        Some(1),
+        None,
+        None,
+    )
+    .unwrap()
+    .with_case(Some("ones"));
+    let error = anytest::build_command(&context).unwrap_err();
+
+    assert!(matches!(error, Error::NoTestAtLine { line_nr: 1, .. }));
+}
+
+#[test]
+fn test_build_command_with_unknown_case_index() {
+    let context = Context::new(
+        Some("tests/fixtures/pytest"),
+        "test_parametrize.py",
+        Some(6),
+        None,
+        None,
+    )
+    .unwrap()
+    .with_case(Some("3"));
+    let error = anytest::build_command(&context).unwrap_err();
+
+    assert!(matches!(error, Error::NoTestAtLine { line_nr: 6, .. }));
+}
+
+#[test]
+fn test_build_command_with_unknown_each_case_index() {
+    let context = Context::new(
+        Some("tests/fixtures/jest"),
+        "__tests__/each-test.js",
+        Some(3),
+        None,
+        None,
+    )
+    .unwrap()
+    .with_case(Some("3"));
+    let error = anytest::build_command(&context).unwrap_err();
+
+    assert!(matches!(error, Error::NoTestAtLine { line_nr: 3, .. }));
+}
+
+#[test]
+fn test_build_command_with_case_unsupported() {
+    let context = Context::new(
+        Some("tests/fixtures/gotest"),
+        "pkg/calc/calc_test.go",
+        Some(5),
+        None,
+        None,
+    )
+    .unwrap()
+    .with_case(Some("1"));
+    let error = anytest::build_command(&context).unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::CaseNotSupported { framework } if framework == "gotest"
+    ));
+}
+
+#[test]
+fn test_build_command_with_case_index_unsupported() {
+    let context = Context::new(
+        Some("tests/fixtures/rspec"),
+        "shared_examples_spec.rb",
+        Some(11),
+        None,
+        None,
+    )
+    .unwrap()
+    .with_case(Some("2"));
+    let error = anytest::build_command(&context).unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::CaseNotSupported { framework } if framework == "rspec"
+    ));
+}
+
+#[test]
+fn test_resolve_command() {
+    let context = Context::new(
+        Some("tests/fixtures/jest"),
+        "__tests__/normal-test.js",
+        Some(4),
+        None,
+        None,
+    )
+    .unwrap();
+    let test_command = anytest::resolve_command(&context).unwrap();
+
+    assert_eq!(test_command.framework(), "jest");
+    assert_eq!(test_command.language(), "javascript");
+    assert_eq!(test_command.test_name(), Some("adds two numbers"));
+    assert_eq!(test_command.test_line_nr(), Some(4));
+    assert_eq!(test_command.namespaces(), ["Math", "Addition"]);
+    assert_eq!(test_command.command().get_program(), "jest");
+}
+
+#[test]
+fn test_resolve_command_file() {
+    let context = Context::new(
+        Some("tests/fixtures/jest"),
+        "__tests__/normal-test.js",
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    let test_command = anytest::resolve_command(&context).unwrap();
+
+    assert_eq!(test_command.test_name(), None);
+    assert_eq!(test_command.test_line_nr(), None);
+    assert!(test_command.namespaces().is_empty());
+}
+
+fn build_commands(contexts: &[(&str, &str, Option<LineNr>)]) -> Vec<String> {
+    let contexts = contexts
+        .iter()
+        .map(|(root, path, line_nr)| Context::new(Some(root), path, *line_nr, None, None).unwrap())
+        .collect::<Vec<_>>();
+
+    anytest::build_commands(&contexts)
+        .unwrap()
+        .iter()
+        .map(anytest::format_command)
+        .collect()
+}
+
+#[test]
+fn test_build_commands() {
+    assert_eq!(
+        build_commands(&[
+            ("tests/fixtures/rspec", "normal_spec.rb", Some(2)),
+            ("tests/fixtures/cargotest/crate", "src/lib.rs", Some(5)),
+            ("tests/fixtures/rspec", "context_spec.rb", Some(3)),
+            (
+                "tests/fixtures/cargotest/crate",
+                "tests/integration_test.rs",
+                Some(3)
+            ),
+            ("tests/fixtures/cargotest/crate", "src/lib.rs", Some(9)),
+            ("tests/fixtures/rspec", "normal_spec.rb", Some(2)),
+        ]),
+        vec![
+            "rspec normal_spec.rb:2 context_spec.rb:3",
+            "cargo test --lib tests::first_test -- tests::second_test --exact",
+            "cargo test --test integration_test it_adds_two -- --exact",
+        ]
+    );
+}
+
+#[test]
+fn test_build_commands_with_file() {
+    assert_eq!(
+        build_commands(&[
+            ("tests/fixtures/cargotest/crate", "src/lib.rs", Some(5)),
+            ("tests/fixtures/cargotest/crate", "src/lib.rs", None),
+            ("tests/fixtures/pytest", "test_class.py", Some(11)),
+            ("tests/fixtures/pytest", "test_parametrize.py", None),
+        ]),
+        vec![
+            "cargo test --lib",
+            "python -m pytest test_class.py::test_function test_parametrize.py",
+        ]
+    );
+}
+
+#[test]
+fn test_build_commands_without_merging() {
+    assert_eq!(
+        build_commands(&[
+            ("tests/fixtures/jest", "__tests__/normal-test.js", Some(4)),
+            ("tests/fixtures/jest", "__tests__/context-test.js", Some(4)),
+        ]),
+        vec![
+            "jest --runTestsByPath -t '^Math Addition adds two numbers$' -- __tests__/normal-test.js",
+            "jest --runTestsByPath -t '^Math Addition adds two numbers$' -- __tests__/context-test.js",
+        ]
+    );
+}
+
+#[test]
+fn test_build_command_in_nested_project() {
+    let root = env::current_dir().unwrap().join("tests/fixtures/monorepo");
+    let context = Context::new(
+        root.to_str(),
+        "packages/web/src/math.test.ts",
+        Some(6),
+        None,
+        None,
+    )
+    .unwrap();
+    let command = anytest::build_command(&context).unwrap();
+
+    assert_eq!(
+        anytest::format_command(&command),
+        "../../node_modules/.bin/vitest run src/math.test.ts -t '^add adds two numbers$'"
+    );
+    assert_eq!(
+        command.get_current_dir(),
+        Some(root.join("packages/web").as_path())
+    );
+}
+
+#[test]
+fn test_build_commands_in_nested_projects() {
+    let root = env::current_dir().unwrap().join("tests/fixtures/monorepo");
+    let contexts = [
+        "services/billing/tests/test_invoice.py",
+        "packages/web/src/math.ts",
+    ]
+    .map(|path| Context::new(root.to_str(), path, None, None, None).unwrap());
+    let commands = anytest::build_commands(&contexts).unwrap();
+
+    assert_eq!(
+        commands
+            .iter()
+            .map(|command| (
+                anytest::format_command(command),
+                command
+                    .get_current_dir()
+                    .unwrap()
+                    .strip_prefix(&root)
+                    .unwrap()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "python -m pytest tests/test_invoice.py".into(),
+                Path::new("services/billing")
+            ),
+            (
+                "../../node_modules/.bin/vitest run src/math.test.ts".into(),
+                Path::new("packages/web")
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_find_alternate_in_nested_project() {
+    let context = Context::new(
+        Some("tests/fixtures/monorepo"),
+        "services/billing/billing/invoice.py",
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(
+        anytest::find_alternate(&context).unwrap(),
+        Path::new("services/billing/tests/test_invoice.py")
+    );
+}
+
+#[test]
+fn test_find_alternate_not_found() {
+    let context =
+        Context::new(Some("tests/fixtures/folder"), "file.txt", None, None, None).unwrap();
+    let error = anytest::find_alternate(&context).unwrap_err();
+
+    assert!(matches!(error, Error::NoAlternate(path) if path.ends_with("folder/file.txt")));
+}
+
+fn git(root: &Path, args: &[&str]) {
+    let status = process::Command::new("git")
+        .args([
+            "-c",
+            "user.name=anytest",
+            "-c",
+            "user.email=anytest@example.com",
+        ])
+        .args(args)
+        .current_dir(root)
+        .output()
+        .unwrap()
+        .status;
+
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+fn write(root: &Path, path: &str, content: &str) {
+    let path = root.join(path);
+
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+#[test]
+fn test_changed_contexts() {
+    let root = env::temp_dir().join(format!("anytest-changed-{}", process::id()));
+    let _ = fs::remove_dir_all(&root);
+
+    write(&root, "lib/calc.rb", "class Calc\nend\n");
+    write(&root, "spec/calc_spec.rb", "describe Calc do\nend\n");
+    write(
+        &root,
+        "src/math.js",
+        "export const add = (a, b) => a + b;\n",
+    );
+    write(&root, "src/math.test.js", "test('add', () => {});\n");
+    write(&root, "README.md", "# Calc\n");
+    git(&root, &["init", "-q", "-b", "main"]);
+    git(&root, &["add", "."]);
+    git(&root, &["commit", "-q", "-m", "Initial commit"]);
+
+    git(&root, &["checkout", "-q", "-b", "feature"]);
+    write(
+        &root,
+        "src/math.js",
+        "export const add = (a, b) => b + a;\n",
+    );
+    git(&root, &["commit", "-q", "-am", "Change math"]);
+    git(&root, &["checkout", "-q", "--orphan", "unrelated"]);
+    git(&root, &["commit", "-q", "-m", "Unrelated history"]);
+    git(&root, &["checkout", "-q", "feature"]);
+
+    write(&root, "lib/calc.rb", "class Calc\n  # TODO\nend\n");
+    write(&root, "README.md", "# Calculator\n");
+    write(&root, "spec/new_spec.rb", "describe New do\nend\n");
+    write(&root, "spec/caf\u{e9}_spec.rb", "describe Cafe do\nend\n");
+
+    let rel = |contexts: Vec<Context>| {
+        contexts
+            .iter()
+            .map(|context| context.rel_str().to_string())
+            .collect::<Vec<_>>()
+    };
+    let root_str = root.to_str();
+
+    assert_eq!(
+        rel(anytest::changed_contexts(root_str, None).unwrap()),
+        vec![
+            "spec/calc_spec.rb",
+            "spec/caf\u{e9}_spec.rb",
+            "spec/new_spec.rb"
+        ]
+    );
+    assert_eq!(
+        rel(anytest::changed_contexts(root_str, Some("main")).unwrap()),
+        vec![
+            "spec/calc_spec.rb",
+            "src/math.test.js",
+            "spec/caf\u{e9}_spec.rb",
+            "spec/new_spec.rb"
+        ]
+    );
+    assert!(matches!(
+        anytest::changed_contexts(root_str, Some("unrelated")).unwrap_err(),
+        Error::NoMergeBase(base) if base == "unrelated"
+    ));
+    assert!(matches!(
+        anytest::changed_contexts(root_str, Some("missing")).unwrap_err(),
+        Error::Git { args, .. } if args[0] == "merge-base"
+    ));
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_is_runnable() {
+    let is_runnable = |root: &str, path: &str| {
+        let context = Context::new(Some(root), path, None, None, None).unwrap();
+
+        anytest::is_runnable(&context).unwrap()
+    };
+
+    assert!(is_runnable("tests/fixtures/rspec", "normal_spec.rb"));
+    assert!(is_runnable(
+        "tests/fixtures/rspec",
+        "app/services/billing.rb"
+    ));
+    assert!(!is_runnable("tests/fixtures/folder", "file.txt"));
+}
@@ -53,7 +53,10 @@ fn test_exunit_without_mix_file() {
 fn test_exunit_without_mix_suite() {
     let project = Project::new("exunit");
 
-    assert_eq!(project.test_suite("normal_test.exs"), "elixir *.exs");
+    assert_eq!(
+        project.test_suite("normal_test.exs"),
+        "elixir -r normal_test.exs -r other_test.exs"
+    );
 }
 
 #[test]
@@ -92,3 +95,25 @@ fn test_exunit_mix_suite() {
 
     assert_eq!(project.test_suite("normal_test.exs"), "mix test");
 }
+
+#[test]
+fn test_exunit_list() {
+    let project = Project::new("exunit/mix");
+
+    assert_eq!(
+        project.list("normal_test.exs"),
+        vec![(6, "numbers".into(), "mix test normal_test.exs:6".into())]
+    );
+}
+
+#[test]
+fn test_exunit_source_file() {
+    let project = Project::new("exunit/mix");
+
+    assert_eq!(
+        project.test_line("lib/calc.ex", 2),
+        "mix test test/calc_test.exs"
+    );
+    assert_eq!(project.alternate("lib/calc.ex"), "test/calc_test.exs");
+    assert_eq!(project.alternate("test/calc_test.exs"), "lib/calc.ex");
+}
@@ -0,0 +1,106 @@
+use common::Project;
+
+mod common;
+
+#[test]
+fn test_gotest_line() {
+    let project = Project::new("gotest");
+
+    assert_eq!(
+        project.test_line("pkg/calc/calc_test.go", 5),
+        "go test ./pkg/calc -run '^TestAdd$'"
+    );
+
+    assert_eq!(
+        project.test_line("pkg/calc/calc_test.go", 7),
+        "go test ./pkg/calc -run '^TestAdd$/^positive_numbers$'"
+    );
+
+    assert_eq!(
+        project.test_line("pkg/calc/calc_test.go", 14),
+        r"go test ./pkg/calc -run '^TestAdd$/^negative$/^a\+b$'"
+    );
+
+    assert_eq!(
+        project.test_line("pkg/calc/calc_test.go", 20),
+        "go test ./pkg/calc -run '^TestAdd$'"
+    );
+
+    assert_eq!(
+        project.test_line("pkg/calc/calc_test.go", 31),
+        "go test ./pkg/calc -run '^$' -bench '^BenchmarkAdd$'"
+    );
+
+    assert_eq!(
+        project.test_line("math_test.go", 6),
+        "go test . -run '^TestMath$'"
+    );
+}
+
+#[test]
+fn test_gotest_line_no_nearest() {
+    let project = Project::new("gotest");
+
+    assert_eq!(
+        project.test_line("pkg/calc/calc_test.go", 3),
+        "go test ./pkg/calc"
+    );
+
+    assert_eq!(
+        project.test_line("pkg/calc/calc_test.go", 26),
+        "go test ./pkg/calc"
+    );
+}
+
+#[test]
+fn test_gotest_file() {
+    let project = Project::new("gotest");
+
+    assert_eq!(
+        project.test_file("pkg/calc/calc_test.go"),
+        "go test ./pkg/calc"
+    );
+}
+
+#[test]
+fn test_gotest_suite() {
+    let project = Project::new("gotest");
+
+    assert_eq!(project.test_suite("pkg/calc/calc_test.go"), "go test ./...");
+}
+
+#[test]
+fn test_gotest_list() {
+    let project = Project::new("gotest");
+
+    assert_eq!(
+        project.list("pkg/calc/calc_test.go"),
+        vec![
+            (
+                5,
+                "TestAdd".into(),
+                "go test ./pkg/calc -run '^TestAdd$'".into()
+            ),
+            (
+                6,
+                "TestAdd positive numbers".into(),
+                "go test ./pkg/calc -run '^TestAdd$/^positive_numbers$'".into()
+            ),
+            (
+                12,
+                "TestAdd negative".into(),
+                "go test ./pkg/calc -run '^TestAdd$/^negative$'".into()
+            ),
+            (
+                13,
+                "TestAdd negative a+b".into(),
+                r"go test ./pkg/calc -run '^TestAdd$/^negative$/^a\+b$'".into()
+            ),
+            (
+                29,
+                "BenchmarkAdd".into(),
+                "go test ./pkg/calc -run '^$' -bench '^BenchmarkAdd$'".into()
+            ),
+        ]
+    );
+}
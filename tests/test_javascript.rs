@@ -82,6 +82,46 @@ fn test_jest_line_react() {
     );
 }
 
+#[test]
+fn test_jest_line_each() {
+    let project = Project::new("jest");
+
+    assert_eq!(
+        project.test_line("__tests__/each-test.js", 1),
+        "jest --runTestsByPath -t '^Math with .*' -- __tests__/each-test.js"
+    );
+    assert_eq!(
+        project.test_line("__tests__/each-test.js", 3),
+        r"jest --runTestsByPath -t '^Math with .* add\(.*, .*\) \-> .*$' -- __tests__/each-test.js"
+    );
+    assert_eq!(
+        project.test_line("__tests__/each-test.js", 10),
+        "jest --runTestsByPath -t '^Math with .* .* squares .*$' -- __tests__/each-test.js"
+    );
+}
+
+#[test]
+fn test_jest_line_each_case() {
+    let project = Project::new("jest");
+
+    assert_eq!(
+        project.test_case("__tests__/each-test.js", 1, "2"),
+        "jest --runTestsByPath -t '^Math with 2' -- __tests__/each-test.js"
+    );
+    assert_eq!(
+        project.test_case("__tests__/each-test.js", 3, "1"),
+        r"jest --runTestsByPath -t '^Math with .* add\(1, 1\) \-> 2$' -- __tests__/each-test.js"
+    );
+    assert_eq!(
+        project.test_case("__tests__/each-test.js", 3, "2"),
+        r"jest --runTestsByPath -t '^Math with .* add\(1, 2\) \-> 3$' -- __tests__/each-test.js"
+    );
+    assert_eq!(
+        project.test_case("__tests__/each-test.js", 10, "1"),
+        "jest --runTestsByPath -t '^Math with .* 0 squares .*$' -- __tests__/each-test.js"
+    );
+}
+
 #[test]
 fn test_jest_line_no_nearest() {
     let project = Project::new("jest");
@@ -121,3 +161,146 @@ fn test_jest_suite() {
         "jest --runTestsByPath"
     );
 }
+
+#[test]
+fn test_jest_yarn() {
+    let project = Project::new("config");
+
+    assert_eq!(
+        project.test_line("__tests__/normal-test.js", 4),
+        "yarn jest --runTestsByPath -t '^Math Addition adds two numbers$' __tests__/normal-test.js"
+    );
+}
+
+#[test]
+fn test_jest_list() {
+    let project = Project::new("jest");
+
+    assert_eq!(
+        project.list("__tests__/normal-test.js"),
+        vec![(
+            4,
+            "Math Addition adds two numbers".into(),
+            "jest --runTestsByPath -t '^Math Addition adds two numbers$' -- __tests__/normal-test.js"
+                .into()
+        )]
+    );
+}
+
+#[test]
+fn test_vitest_line() {
+    let project = Project::new("vitest");
+
+    assert_eq!(
+        project.test_line("math.test.ts", 3),
+        "vitest run math.test.ts -t '^Math'"
+    );
+
+    assert_eq!(
+        project.test_line("math.test.ts", 5),
+        r"vitest run math.test.ts -t '^Math Addition \(sum\) adds two numbers$'"
+    );
+
+    assert_eq!(
+        project.test_line("math.test.ts", 11),
+        "vitest run math.test.ts -t 'standalone$'"
+    );
+}
+
+#[test]
+fn test_vitest_line_no_nearest() {
+    let project = Project::new("vitest");
+
+    assert_eq!(
+        project.test_line("math.test.ts", 1),
+        "vitest run math.test.ts"
+    );
+}
+
+#[test]
+fn test_vitest_file() {
+    let project = Project::new("vitest");
+
+    assert_eq!(project.test_file("math.test.ts"), "vitest run math.test.ts");
+}
+
+#[test]
+fn test_vitest_suite() {
+    let project = Project::new("vitest");
+
+    assert_eq!(project.test_suite("math.test.ts"), "vitest run");
+}
+
+#[test]
+fn test_vitest_vite_config() {
+    let project = Project::new("vite");
+
+    assert_eq!(
+        project.test_line("math.spec.js", 2),
+        "vitest run math.spec.js -t '^Math adds two numbers$'"
+    );
+}
+
+#[test]
+fn test_mocha_line() {
+    let project = Project::new("mocha");
+
+    assert_eq!(
+        project.test_line("test/math.js", 3),
+        "mocha test/math.js --grep '^Math '"
+    );
+
+    assert_eq!(
+        project.test_line("test/math.js", 4),
+        "mocha test/math.js --grep '^Math Addition '"
+    );
+
+    assert_eq!(
+        project.test_line("test/math.js", 5),
+        r"mocha test/math.js --grep '^Math Addition adds two numbers \(1 \+ 1\)$'"
+    );
+}
+
+#[test]
+fn test_mocha_line_no_nearest() {
+    let project = Project::new("mocha");
+
+    assert_eq!(project.test_line("test/math.js", 1), "mocha test/math.js");
+}
+
+#[test]
+fn test_mocha_project_in_test_directory() {
+    let project = Project::new("test/mocha-app");
+
+    assert_eq!(project.test_file("src/math.js"), "mocha test/math.spec.js");
+    assert_eq!(
+        project.test_file("test/math.spec.js"),
+        "mocha test/math.spec.js"
+    );
+}
+
+#[test]
+fn test_mocha_file() {
+    let project = Project::new("mocha");
+
+    assert_eq!(project.test_file("test/math.js"), "mocha test/math.js");
+}
+
+#[test]
+fn test_mocha_suite() {
+    let project = Project::new("mocha");
+
+    assert_eq!(project.test_suite("test/math.js"), "mocha");
+}
+
+#[test]
+fn test_jest_source_file() {
+    let project = Project::new("jest");
+
+    assert_eq!(
+        project.test_line("src/math.ts", 1),
+        "jest --runTestsByPath -- src/math.test.ts"
+    );
+    assert_eq!(project.alternate("src/math.ts"), "src/math.test.ts");
+    assert_eq!(project.alternate("src/math.test.ts"), "src/math.ts");
+}
@@ -1,3 +1,4 @@
+use anytest::Context;
 use common::Project;
 
 mod common;
@@ -32,6 +33,24 @@ fn test_pytest_line() {
     );
 }
 
+#[test]
+fn test_pytest_line_parametrize() {
+    let project = Project::new("pytest");
+
+    assert_eq!(
+        project.test_line("test_parametrize.py", 6),
+        "python -m pytest test_parametrize.py::test_add"
+    );
+    assert_eq!(
+        project.test_case("test_parametrize.py", 6, "twos"),
+        "python -m pytest 'test_parametrize.py::test_add[twos]'"
+    );
+    assert_eq!(
+        project.test_case("test_parametrize.py", 6, "1"),
+        "python -m pytest 'test_parametrize.py::test_add[ones]'"
+    );
+}
+
 #[test]
 fn test_pytest_xunit_line() {
     let project = Project::new("nose");
@@ -193,3 +212,217 @@ fn test_pytest_pdm_suite() {
         "pdm run python -m pytest"
     );
 }
+
+#[test]
+fn test_pytest_list() {
+    let project = Project::new("pytest");
+
+    assert_eq!(
+        project.list("test_class.py"),
+        vec![
+            (
+                3,
+                "TestClass TestNestedClass test_nestedclass_method".into(),
+                "python -m pytest test_class.py::TestClass::TestNestedClass::test_nestedclass_method"
+                    .into()
+            ),
+            (
+                6,
+                "TestClass test_method".into(),
+                "python -m pytest test_class.py::TestClass::test_method".into()
+            ),
+            (
+                10,
+                "test_function".into(),
+                "python -m pytest test_class.py::test_function".into()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_nose2_line() {
+    let project = Project::new("nose2");
+
+    assert_eq!(
+        project.test_line("tests/test_class.py", 1),
+        "python -m nose2 tests.test_class.TestNumbers"
+    );
+
+    assert_eq!(
+        project.test_line("tests/test_class.py", 2),
+        "python -m nose2 tests.test_class.TestNumbers.test_numbers"
+    );
+
+    assert_eq!(
+        project.test_line("tests/test_class.py", 14),
+        "python -m nose2 tests.test_class.UnittestClass.test_unittest"
+    );
+
+    assert_eq!(
+        project.test_line("tests/test_method.py", 3),
+        "python -m nose2 tests.test_method.test_numbers"
+    );
+}
+
+#[test]
+fn test_nose2_file() {
+    let project = Project::new("nose2");
+
+    assert_eq!(
+        project.test_file("tests/test_class.py"),
+        "python -m nose2 tests.test_class"
+    );
+}
+
+#[test]
+fn test_nose2_suite() {
+    let project = Project::new("nose2");
+
+    assert_eq!(project.test_suite("tests/test_class.py"), "python -m nose2");
+}
+
+#[test]
+fn test_pyunit_line() {
+    let project = Project::new("pyunit");
+
+    assert_eq!(
+        project.test_line("tests/test_numbers.py", 4),
+        "python -m unittest tests.test_numbers.TestNumbers"
+    );
+
+    assert_eq!(
+        project.test_line("tests/test_numbers.py", 6),
+        "python -m unittest tests.test_numbers.TestNumbers.test_addition"
+    );
+
+    assert_eq!(
+        project.test_line("tests/test_numbers.py", 10),
+        "python -m unittest tests.test_numbers.TestNumbers.TestNested.test_nested"
+    );
+}
+
+#[test]
+fn test_pyunit_line_no_nearest() {
+    let project = Project::new("pyunit");
+
+    assert_eq!(
+        project.test_line("tests/test_numbers.py", 1),
+        "python -m unittest tests.test_numbers"
+    );
+}
+
+#[test]
+fn test_pyunit_file() {
+    let project = Project::new("pyunit");
+
+    assert_eq!(
+        project.test_file("tests/test_numbers.py"),
+        "python -m unittest tests.test_numbers"
+    );
+}
+
+#[test]
+fn test_pyunit_suite() {
+    let project = Project::new("pyunit");
+
+    assert_eq!(
+        project.test_suite("tests/test_numbers.py"),
+        "python -m unittest"
+    );
+}
+
+#[test]
+fn test_pyunit_with_pytest_config() {
+    let project = Project::new("pyunit-pytest");
+
+    assert_eq!(
+        project.test_line("test_numbers.py", 6),
+        "python -m pytest test_numbers.py::TestNumbers::test_addition"
+    );
+}
+
+#[test]
+fn test_pytest_with_unittest_mock() {
+    let project = Project::new("pytest-mock");
+
+    assert_eq!(
+        project.test_line("tests/test_foo.py", 4),
+        "python -m pytest tests/test_foo.py::test_foo"
+    );
+}
+
+#[test]
+fn test_djangotest_line() {
+    let project = Project::new("django");
+
+    assert_eq!(
+        project.test_line("polls/tests.py", 4),
+        "python manage.py test polls.tests.QuestionModelTests"
+    );
+
+    assert_eq!(
+        project.test_line("polls/tests.py", 6),
+        "python manage.py test polls.tests.QuestionModelTests.test_was_published_recently"
+    );
+
+    assert_eq!(
+        project.test_line("polls/tests.py", 9),
+        "python manage.py test polls.tests.QuestionModelTests.test_str"
+    );
+}
+
+#[test]
+fn test_djangotest_file() {
+    let project = Project::new("django");
+
+    assert_eq!(
+        project.test_file("polls/tests.py"),
+        "python manage.py test polls.tests"
+    );
+}
+
+#[test]
+fn test_djangotest_source_file() {
+    let context = Context::new(
+        Some("tests/fixtures/django"),
+        "polls/latest.py",
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(!anytest::is_runnable(&context).unwrap());
+}
+
+#[test]
+fn test_djangotest_suite() {
+    let project = Project::new("django");
+
+    assert_eq!(
+        project.test_suite("polls/tests.py"),
+        "python manage.py test"
+    );
+}
+
+#[test]
+fn test_djangotest_pipenv_line() {
+    let project = Project::new("django-pipenv");
+
+    assert_eq!(
+        project.test_line("polls/tests.py", 9),
+        "pipenv run python manage.py test polls.tests.QuestionModelTests.test_str"
+    );
+}
+
+#[test]
+fn test_pytest_source_file() {
+    let project = Project::new("pytest");
+
+    assert_eq!(
+        project.test_line("pkg/billing.py", 2),
+        "python -m pytest tests/test_billing.py"
+    );
+    assert_eq!(project.alternate("pkg/billing.py"), "tests/test_billing.py");
+}
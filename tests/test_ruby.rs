@@ -32,6 +32,20 @@ fn test_rspec_line2() {
     );
 }
 
+#[test]
+fn test_rspec_line_shared_examples() {
+    let project = Project::new("rspec");
+
+    assert_eq!(
+        project.test_line("shared_examples_spec.rb", 8),
+        "rspec shared_examples_spec.rb:8"
+    );
+    assert_eq!(
+        project.test_case("shared_examples_spec.rb", 11, "includes 2"),
+        "rspec shared_examples_spec.rb:11 -e 'includes 2'"
+    );
+}
+
 #[test]
 fn test_rspec_file() {
     let project = Project::new("rspec");
@@ -50,7 +64,7 @@ fn test_rspec_file_starts_with_test() {
 fn test_rspec_suite() {
     let project = Project::new("rspec");
 
-    assert_eq!(project.test_suite("normal_spec.rb"), "rspec ");
+    assert_eq!(project.test_suite("normal_spec.rb"), "rspec");
 }
 
 #[test]
@@ -62,3 +76,123 @@ fn test_rspec_turnip() {
         "rspec spec/math.feature"
     );
 }
+
+#[test]
+fn test_rspec_configured() {
+    let project = Project::new("config");
+
+    assert_eq!(
+        project.test_line("normal_spec.rb", 3),
+        "bin/rspec --require rails_helper normal_spec.rb:3"
+    );
+}
+
+#[test]
+fn test_rspec_list() {
+    let project = Project::new("rspec");
+
+    assert_eq!(
+        project.list("context_spec.rb"),
+        vec![(
+            3,
+            "Math Addition adds two numbers".into(),
+            "rspec context_spec.rb:3".into()
+        )]
+    );
+}
+
+#[test]
+fn test_minitest_line() {
+    let project = Project::new("minitest");
+
+    assert_eq!(
+        project.test_line("math_test.rb", 3),
+        "bundle exec ruby -Itest math_test.rb -n '/^MathTest#/'"
+    );
+
+    assert_eq!(
+        project.test_line("math_test.rb", 4),
+        "bundle exec ruby -Itest math_test.rb -n '/^MathTest#test_addition$/'"
+    );
+
+    assert_eq!(
+        project.test_line("math_test.rb", 8),
+        "bundle exec ruby -Itest math_test.rb -n '/^MathTest#test_adds_two_numbers$/'"
+    );
+}
+
+#[test]
+fn test_minitest_line_no_nearest() {
+    let project = Project::new("minitest");
+
+    assert_eq!(
+        project.test_line("math_test.rb", 1),
+        "bundle exec ruby -Itest math_test.rb"
+    );
+}
+
+#[test]
+fn test_minitest_file() {
+    let project = Project::new("minitest");
+
+    assert_eq!(
+        project.test_file("math_test.rb"),
+        "bundle exec ruby -Itest math_test.rb"
+    );
+}
+
+#[test]
+fn test_minitest_suite() {
+    let project = Project::new("minitest");
+
+    assert_eq!(project.test_suite("math_test.rb"), "bundle exec rake test");
+}
+
+#[test]
+fn test_minitest_rails() {
+    let project = Project::new("rails");
+
+    assert_eq!(
+        project.test_line("test/models/user_test.rb", 4),
+        "bin/rails test test/models/user_test.rb:4"
+    );
+
+    assert_eq!(
+        project.test_file("test/models/user_test.rb"),
+        "bin/rails test test/models/user_test.rb"
+    );
+
+    assert_eq!(
+        project.test_suite("test/models/user_test.rb"),
+        "bin/rails test"
+    );
+}
+
+#[test]
+fn test_rspec_source_file() {
+    let project = Project::new("rspec");
+
+    assert_eq!(
+        project.test_line("app/services/billing.rb", 3),
+        "rspec spec/services/billing_spec.rb"
+    );
+    assert_eq!(
+        project.alternate("app/services/billing.rb"),
+        "spec/services/billing_spec.rb"
+    );
+    assert_eq!(
+        project.alternate("spec/services/billing_spec.rb"),
+        "app/services/billing.rb"
+    );
+}
+
+#[test]
+fn test_rspec_configured_projections() {
+    let project = Project::new("config");
+
+    assert_eq!(
+        project.test_file("lib/calc.rb"),
+        "bin/rspec --require rails_helper spec/unit/calc_spec.rb"
+    );
+    assert_eq!(project.alternate("spec/unit/calc_spec.rb"), "lib/calc.rb");
+}
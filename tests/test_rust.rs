@@ -8,17 +8,17 @@ fn test_cargotest_line_on_lib() {
 
     assert_eq!(
         project.test_line("src/lib.rs", 5),
-        "cargo test tests::first_test -- --exact"
+        "cargo test --lib tests::first_test -- --exact"
     );
 
     assert_eq!(
         project.test_line("src/lib.rs", 13),
-        "cargo test tests::third_test -- --exact"
+        "cargo test --lib tests::third_test -- --exact"
     );
 
     assert_eq!(
         project.test_line("src/lib.rs", 7),
-        "cargo test tests::second_test -- --exact"
+        "cargo test --lib tests::second_test -- --exact"
     );
 }
 
@@ -28,17 +28,17 @@ fn test_cargotest_line_on_modules_with_mod_as_test() {
 
     assert_eq!(
         project.test_line("src/somemod_test.rs", 5),
-        "cargo test somemod_test::test::first_test -- --exact"
+        "cargo test --lib somemod_test::test::first_test -- --exact"
     );
 
     assert_eq!(
         project.test_line("src/somemod_test.rs", 13),
-        "cargo test somemod_test::test::third_test -- --exact"
+        "cargo test --lib somemod_test::test::third_test -- --exact"
     );
 
     assert_eq!(
         project.test_line("src/somemod_test.rs", 7),
-        "cargo test somemod_test::test::second_test -- --exact"
+        "cargo test --lib somemod_test::test::second_test -- --exact"
     );
 }
 
@@ -48,17 +48,17 @@ fn test_cargotest_line_without_mod() {
 
     assert_eq!(
         project.test_line("src/nomod.rs", 2),
-        "cargo test nomod::first_test -- --exact"
+        "cargo test --lib nomod::first_test -- --exact"
     );
 
     assert_eq!(
         project.test_line("src/nomod.rs", 10),
-        "cargo test nomod::third_test -- --exact"
+        "cargo test --lib nomod::third_test -- --exact"
     );
 
     assert_eq!(
         project.test_line("src/nomod.rs", 6),
-        "cargo test nomod::second_test -- --exact"
+        "cargo test --lib nomod::second_test -- --exact"
     );
 }
 
@@ -68,47 +68,47 @@ fn test_cargotest_line_on_modules() {
 
     assert_eq!(
         project.test_line("src/somemod.rs", 5),
-        "cargo test somemod::tests::first_test -- --exact"
+        "cargo test --lib somemod::tests::first_test -- --exact"
     );
 
     assert_eq!(
         project.test_line("src/somemod.rs", 13),
-        "cargo test somemod::tests::third_test -- --exact"
+        "cargo test --lib somemod::tests::third_test -- --exact"
     );
 
     assert_eq!(
         project.test_line("src/somemod.rs", 7),
-        "cargo test somemod::tests::second_test -- --exact"
+        "cargo test --lib somemod::tests::second_test -- --exact"
     );
 
     assert_eq!(
         project.test_line("src/nested/mod.rs", 5),
-        "cargo test nested::tests::first_test -- --exact"
+        "cargo test --lib nested::tests::first_test -- --exact"
     );
 
     assert_eq!(
         project.test_line("src/nested/mod.rs", 13),
-        "cargo test nested::tests::third_test -- --exact"
+        "cargo test --lib nested::tests::third_test -- --exact"
     );
 
     assert_eq!(
         project.test_line("src/nested/mod.rs", 7),
-        "cargo test nested::tests::second_test -- --exact"
+        "cargo test --lib nested::tests::second_test -- --exact"
     );
 
     assert_eq!(
         project.test_line("src/too/nested.rs", 5),
-        "cargo test too::nested::tests::first_test -- --exact"
+        "cargo test --lib too::nested::tests::first_test -- --exact"
     );
 
     assert_eq!(
         project.test_line("src/too/nested.rs", 13),
-        "cargo test too::nested::tests::third_test -- --exact"
+        "cargo test --lib too::nested::tests::third_test -- --exact"
     );
 
     assert_eq!(
         project.test_line("src/too/nested.rs", 7),
-        "cargo test too::nested::tests::second_test -- --exact"
+        "cargo test --lib too::nested::tests::second_test -- --exact"
     );
 }
 
@@ -118,7 +118,7 @@ fn test_cargotest_line_async_tokio() {
 
     assert_eq!(
         project.test_line("src/lib.rs", 15),
-        "cargo test tests::tokio_async_test -- --exact"
+        "cargo test --lib tests::tokio_async_test -- --exact"
     );
 }
 
@@ -128,7 +128,51 @@ fn test_cargotest_line_rstest() {
 
     assert_eq!(
         project.test_line("src/lib.rs", 22),
-        "cargo test tests::rstest_test -- --exact"
+        "cargo test --lib tests::rstest_test::"
+    );
+}
+
+#[test]
+fn test_cargotest_line_rstest_case() {
+    let project = Project::new("cargotest/crate");
+
+    assert_eq!(
+        project.test_line("src/lib.rs", 33),
+        "cargo test --lib tests::rstest_case_test::"
+    );
+    assert_eq!(
+        project.test_case("src/lib.rs", 33, "1"),
+        "cargo test --lib tests::rstest_case_test::case_1 -- --exact"
+    );
+    assert_eq!(
+        project.test_case("src/lib.rs", 31, "2"),
+        "cargo test --lib tests::rstest_case_test::case_2_two -- --exact"
+    );
+    assert_eq!(
+        project.test_case("src/lib.rs", 22, "2"),
+        "cargo test --lib tests::rstest_test::case_2 -- --exact"
+    );
+}
+
+#[test]
+fn test_cargotest_line_test_case() {
+    let project = Project::new("cargotest/crate");
+
+    assert_eq!(
+        project.test_line("src/lib.rs", 38),
+        "cargo test --lib tests::test_case_test::"
+    );
+    assert_eq!(
+        project.test_case("src/lib.rs", 38, "1"),
+        "cargo test --lib tests::test_case_test::one -- --exact"
+    );
+    assert_eq!(
+        project.test_case("src/lib.rs", 38, "2"),
+        "cargo test --lib tests::test_case_test::_2 -- --exact"
+    );
+    assert_eq!(
+        project.test_case("src/lib.rs", 38, "one"),
+        "cargo test --lib tests::test_case_test::one -- --exact"
     );
 }
 
@@ -138,7 +182,7 @@ fn test_cargotest_line_async_actix_rt() {
 
     assert_eq!(
         project.test_line("src/lib.rs", 26),
-        "cargo test tests::test_actix_rt -- --exact"
+        "cargo test --lib tests::test_actix_rt -- --exact"
     );
 }
 
@@ -156,20 +200,23 @@ fn test_cargotest_line_integration_test() {
 fn test_cargotest_file() {
     let project = Project::new("cargotest/crate");
 
-    assert_eq!(project.test_file("src/lib.rs"), "cargo test");
+    assert_eq!(project.test_file("src/lib.rs"), "cargo test --lib");
 
-    assert_eq!(project.test_file("src/main.rs"), "cargo test");
+    assert_eq!(project.test_file("src/main.rs"), "cargo test --bin crate");
 
-    assert_eq!(project.test_file("src/somemod.rs"), "cargo test somemod::");
+    assert_eq!(
+        project.test_file("src/somemod.rs"),
+        "cargo test --lib somemod::"
+    );
 
     assert_eq!(
         project.test_file("src/nested/mod.rs"),
-        "cargo test nested::"
+        "cargo test --lib nested::"
     );
 
     assert_eq!(
         project.test_file("src/too/nested.rs"),
-        "cargo test too::nested::"
+        "cargo test --lib too::nested::"
     );
 }
 
@@ -202,6 +249,268 @@ fn test_cargotest_workspace_file() {
 
     assert_eq!(
         project.test_file("crate/src/lib.rs"),
-        "cargo test --package crate"
+        "cargo test --package crate --lib"
+    );
+}
+
+#[test]
+fn test_cargotest_line_on_attribute() {
+    let project = Project::new("cargotest/crate");
+
+    assert_eq!(
+        project.test_line("src/lib.rs", 2),
+        "cargo test --lib tests::first_test -- --exact"
+    );
+
+    assert_eq!(
+        project.test_line("src/nomod.rs", 1),
+        "cargo test --lib nomod::first_test -- --exact"
+    );
+}
+
+#[test]
+fn test_cargotest_list() {
+    let project = Project::new("cargotest/crate");
+
+    assert_eq!(
+        project.list("src/nomod.rs"),
+        vec![
+            (
+                1,
+                "first_test".into(),
+                "cargo test --lib nomod::first_test -- --exact".into()
+            ),
+            (
+                5,
+                "second_test".into(),
+                "cargo test --lib nomod::second_test -- --exact".into()
+            ),
+            (
+                9,
+                "third_test".into(),
+                "cargo test --lib nomod::third_test -- --exact".into()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_nextest_line() {
+    let project = Project::new("nextest");
+
+    assert_eq!(
+        project.test_line("crate/src/lib.rs", 5),
+        "cargo nextest run -E 'package(crate) & kind(lib) & test(=tests::first_test)'"
+    );
+
+    assert_eq!(
+        project.test_line("crate/src/somemod.rs", 7),
+        "cargo nextest run -E 'package(crate) & kind(lib) & test(=somemod::tests::second_test)'"
+    );
+
+    assert_eq!(
+        project.test_line("crate/tests/integration_test.rs", 3),
+        "cargo nextest run -E 'package(crate) & kind(test) & binary(=integration_test) & test(=it_adds_two)'"
+    );
+}
+
+#[test]
+fn test_nextest_line_rstest() {
+    let project = Project::new("nextest");
+
+    assert_eq!(
+        project.test_line("crate/src/lib.rs", 22),
+        "cargo nextest run -E 'package(crate) & kind(lib) & test(/^tests::rstest_test::/)'"
+    );
+    assert_eq!(
+        project.test_case("crate/src/lib.rs", 22, "1"),
+        "cargo nextest run -E 'package(crate) & kind(lib) & test(=tests::rstest_test::case_1)'"
+    );
+}
+
+#[test]
+fn test_nextest_file() {
+    let project = Project::new("nextest");
+
+    assert_eq!(
+        project.test_file("crate/src/lib.rs"),
+        "cargo nextest run -E 'package(crate) & kind(lib)'"
+    );
+
+    assert_eq!(
+        project.test_file("crate/src/somemod.rs"),
+        "cargo nextest run -E 'package(crate) & kind(lib) & test(/^somemod::/)'"
+    );
+
+    assert_eq!(
+        project.test_file("crate/tests/integration_test.rs"),
+        "cargo nextest run -E 'package(crate) & kind(test) & binary(=integration_test)'"
+    );
+}
+
+#[test]
+fn test_nextest_suite() {
+    let project = Project::new("nextest");
+
+    assert_eq!(project.test_suite("crate/src/lib.rs"), "cargo nextest run");
+}
+
+#[test]
+fn test_nextest_enabled_without_executable() {
+    let project = Project::new("nextest-config");
+
+    // `cargo-nextest` isn't installed, so the config alone doesn't select it
+    if which::which("cargo-nextest").is_err() {
+        assert_eq!(project.test_file("src/somemod.rs"), "cargo test somemod::");
+    }
+}
+
+#[test]
+fn test_cargotest_workspace_targets_line() {
+    let project = Project::new("cargo-workspace");
+
+    assert_eq!(
+        project.test_line("crates/foo-core/src/core.rs", 9),
+        "cargo test --package foo_core --lib tests::it_works -- --exact"
+    );
+
+    assert_eq!(
+        project.test_line("crates/foo-core/src/parser/mod.rs", 6),
+        "cargo test --package foo_core --lib parser::tests::it_parses -- --exact"
+    );
+
+    assert_eq!(
+        project.test_line("crates/foo-core/src/bin/cli.rs", 6),
+        "cargo test --package foo_core --bin cli tests::it_runs -- --exact"
+    );
+
+    assert_eq!(
+        project.test_line("crates/foo-core/tests/api/main.rs", 4),
+        "cargo test --package foo_core --test api it_serves -- --exact"
+    );
+
+    assert_eq!(
+        project.test_line("crates/foo-core/tests/api/users.rs", 2),
+        "cargo test --package foo_core --test api users::it_lists_users -- --exact"
+    );
+
+    assert_eq!(
+        project.test_line("crates/foo-core/examples/demo.rs", 4),
+        "cargo test --package foo_core --example demo it_demos -- --exact"
+    );
+
+    assert_eq!(
+        project.test_line("crates/foo-core/benches/speed.rs", 2),
+        "cargo test --package foo_core --bench speed it_is_fast -- --exact"
+    );
+}
+
+#[test]
+fn test_cargotest_workspace_targets_file() {
+    let project = Project::new("cargo-workspace");
+
+    assert_eq!(
+        project.test_file("crates/foo-core/src/core.rs"),
+        "cargo test --package foo_core --lib"
+    );
+
+    assert_eq!(
+        project.test_file("crates/foo-core/src/parser/mod.rs"),
+        "cargo test --package foo_core --lib parser::"
+    );
+
+    assert_eq!(
+        project.test_file("crates/foo-core/tests/api/users.rs"),
+        "cargo test --package foo_core --test api users::"
+    );
+}
+
+#[test]
+fn test_cargotest_line_doctest() {
+    let project = Project::new("cargotest/crate");
+
+    assert_eq!(
+        project.test_line("src/doctest.rs", 10),
+        "cargo test --doc doctest::add"
+    );
+
+    assert_eq!(
+        project.test_line("src/doctest.rs", 9),
+        "cargo test --doc doctest::add"
+    );
+
+    assert_eq!(
+        project.test_line("src/doctest.rs", 22),
+        "cargo test --doc doctest::Calculator::mul"
+    );
+
+    assert_eq!(
+        project.test_line("src/doctest.rs", 32),
+        "cargo test --doc doctest::inner::ANSWER"
+    );
+
+    assert_eq!(
+        project.test_line("src/doctest.rs", 4),
+        "cargo test --doc doctest"
+    );
+
+    assert_eq!(
+        project.test_line("src/doctest.rs", 41),
+        "cargo test --lib doctest::tests::it_adds -- --exact"
+    );
+}
+
+#[test]
+fn test_cargotest_line_doc_comment_outside_code_block() {
+    let project = Project::new("cargotest/crate");
+
+    assert_eq!(
+        project.test_line("src/doctest.rs", 7),
+        "cargo test --lib doctest::"
+    );
+}
+
+#[test]
+fn test_cargotest_workspace_doctest() {
+    let project = Project::new("cargotest");
+
+    assert_eq!(
+        project.test_line("crate/src/doctest.rs", 10),
+        "cargo test --package crate --doc doctest::add"
+    );
+}
+
+#[test]
+fn test_cargotest_line_nested_modules() {
+    let project = Project::new("cargo-workspace");
+
+    assert_eq!(
+        project.test_line("crates/foo-core/src/core.rs", 15),
+        "cargo test --package foo_core --lib tests::parsing::it_parses_nested -- --exact"
+    );
+}
+
+#[test]
+fn test_cargotest_line_declared_modules() {
+    let project = Project::new("cargo-workspace");
+
+    assert_eq!(
+        project.test_line("crates/foo-core/src/parser/lexer/token.rs", 2),
+        "cargo test --package foo_core --lib parser::lexer::token::it_tokenizes -- --exact"
+    );
+
+    assert_eq!(
+        project.test_line("crates/foo-core/src/parser/parser_tests.rs", 2),
+        "cargo test --package foo_core --lib parser::external_tests::it_parses_externally -- --exact"
+    );
+
+    assert_eq!(
+        project.test_line("crates/foo-core/src/utils/helpers.rs", 4),
+        "cargo test --package foo_core --lib util::tests::it_helps -- --exact"
+    );
+
+    assert_eq!(
+        project.test_file("crates/foo-core/src/utils/helpers.rs"),
+        "cargo test --package foo_core --lib util::"
     );
 }
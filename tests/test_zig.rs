@@ -25,3 +25,24 @@ fn test_zigtest_suite() {
 
     assert_eq!(project.test_suite("normal.zig"), "zig build test");
 }
+
+#[test]
+fn test_zigtest_list() {
+    let project = Project::new("zigtest");
+
+    assert_eq!(
+        project.list("normal.zig"),
+        vec![
+            (
+                4,
+                "numbers".into(),
+                "zig test normal.zig --test-filter numbers".into()
+            ),
+            (
+                8,
+                "numbers 2".into(),
+                "zig test normal.zig --test-filter 'numbers 2'".into()
+            ),
+        ]
+    );
+}